@@ -1,25 +1,23 @@
-use crate::email_address::{new};
-use structopt::StructOpt;
+use crate::email_address::EmailAddress;
+use clap::Parser;
+use constrained_type::cli::constrained;
 
 fn main() {
-    let args = Cli::from_args();
+    let args = Cli::parse();
 
-    let res = new("email-address", &args.email, None);
-
-    match res {
-        Ok(v) => { println!("{}", v.value()); }
-        Err(_) => { println!("{:?}", res); }
-    }
+    println!("{}", args.email.value());
 }
 
-#[derive(StructOpt)]
+#[derive(Parser)]
 struct Cli {
-    pub email: String,
+    #[arg(value_parser = constrained::<EmailAddress>())]
+    pub email: EmailAddress,
 }
 
 mod email_address {
     use constrained_type::error::ConstrainedTypeResult;
     use constrained_type::string_like::new_string_like;
+    use constrained_type::validate::Validate;
     use fancy_regex::Regex;
 
     pub(crate) const EMAIL_PATTERN: &str = r".+@.+";
@@ -37,10 +35,18 @@ mod email_address {
         }
     }
 
+    impl Validate for EmailAddress {
+        type Raw = String;
+
+        fn validate(field_name: &str, raw: String) -> ConstrainedTypeResult<EmailAddress> {
+            new(field_name, &raw, None)
+        }
+    }
+
     pub fn new(field_name: &str, raw: &str, err_val: Option<&str>) -> ConstrainedTypeResult<EmailAddress> {
         new_string_like(
             field_name,
-            |v| EmailAddress::new(v),
+            EmailAddress::new,
             Regex::new(EMAIL_PATTERN).unwrap(),
             raw,
             err_val,
@@ -1,20 +1,17 @@
-use crate::handle::{new};
-use structopt::StructOpt;
+use crate::handle::Handle;
+use clap::Parser;
+use constrained_type::cli::constrained;
 
 fn main() {
-    let args = Cli::from_args();
+    let args = Cli::parse();
 
-    let res = new("handle", &args.handle, None);
-
-    match res {
-        Ok(v) => { println!("{}", v.value()); }
-        Err(_) => { println!("{:?}", res); }
-    }
+    println!("{}", args.handle.value());
 }
 
-#[derive(StructOpt)]
+#[derive(Parser)]
 struct Cli {
-    pub handle: String,
+    #[arg(value_parser = constrained::<Handle>())]
+    pub handle: Handle,
 }
 
 ///
@@ -34,13 +31,21 @@ struct Cli {
 /// [a-z0-9]{0,1}                                Require that the last character is a lower-case letter or number
 /// $                                            Match the end of the string.
 ///
+/// Additionally a handle must not be one of the reserved terms.
+///
 mod handle {
+    use constrained_type::blocklist::{new_unreserved, Blocklist, MatchMode};
     use constrained_type::error::ConstrainedTypeResult;
-    use constrained_type::string_like::new_string_like;
+    use constrained_type::string_like::{new_string_like, Pattern};
+    use constrained_type::validate::Validate;
     use fancy_regex::Regex;
 
     pub const HANDLE_PATTERN: &str = r"^[a-z][a-z0-9\-]{0,62}(?<!\-)[a-z0-9]{0,1}$";
 
+    pub const HANDLE_DESCRIPTION: &str = "a lower-case name of up to 64 letters, numbers and hyphens";
+
+    pub const RESERVED: [&str; 3] = ["admin", "root", "support"];
+
     #[derive(Debug, Clone, PartialEq, Eq)]
     pub struct Handle(pub(crate) String);
 
@@ -54,85 +59,90 @@ mod handle {
         }
     }
 
+    impl Validate for Handle {
+        type Raw = String;
+
+        fn validate(field_name: &str, raw: String) -> ConstrainedTypeResult<Handle> {
+            new(field_name, &raw, None)
+        }
+    }
+
     impl std::fmt::Display for Handle {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             write!(f, "{}", self.value())
         }
     }
 
+    pub fn pattern() -> Pattern {
+        Pattern::new(Regex::new(HANDLE_PATTERN).unwrap())
+            .with_description(HANDLE_DESCRIPTION)
+            .with_rule(Regex::new(r"^[a-z]").unwrap(), "must start with a lower-case letter")
+            .with_rule(Regex::new(r"^[a-z0-9\-]*$").unwrap(), "must only contain lower-case letters, numbers and hyphens")
+            .with_rule(Regex::new(r"(?<!\-)$").unwrap(), "must not end with a hyphen")
+            .with_rule(Regex::new(r"^.{0,64}$").unwrap(), "must not be longer than 64 characters")
+    }
+
     pub fn new(field_name: &str, raw: &str, err_val: Option<&str>) -> ConstrainedTypeResult<Handle> {
         new_string_like(
             field_name,
-            |v| Handle::new(v),
-            Regex::new(HANDLE_PATTERN).unwrap(),
+            |v| v,
+            pattern(),
             raw,
             err_val,
-        )
+        ).and_then(|v| new_unreserved(
+            field_name,
+            Handle::new,
+            &Blocklist::new(RESERVED.iter().copied(), MatchMode::Exact),
+            v,
+        ))
     }
 }
 
 #[cfg(test)]
 pub mod test {
-    use crate::handle::{new, HANDLE_PATTERN, Handle};
-    use constrained_type::error::ConstrainedTypeErrorKind::InvalidPattern;
+    use crate::handle::{new, HANDLE_DESCRIPTION, HANDLE_PATTERN, Handle};
+    use constrained_type::error::ConstrainedTypeErrorKind::{InvalidPattern, Reserved};
     use constrained_type::error::ConstrainedTypeError;
 
-    #[test]
-    fn test_handle() {
-        assert_eq!(
-            new("handle", "1", None),
-            ConstrainedTypeError::from(InvalidPattern {
-                field_name: "handle".to_string(),
-                expected: HANDLE_PATTERN.to_string(),
-                found: "1".to_string(),
-            }).into()
-        );
+    fn assert_invalid_pattern(raw: &str, rules: &[&str]) {
+        let err = new("handle", raw, None).unwrap_err();
 
         assert_eq!(
-            new("handle", "1a", None),
+            err,
             ConstrainedTypeError::from(InvalidPattern {
                 field_name: "handle".to_string(),
                 expected: HANDLE_PATTERN.to_string(),
-                found: "1a".to_string(),
-            }).into()
+                found: raw.to_string(),
+            })
         );
 
-        assert_eq!(
-            new("handle", "-a", None),
-            ConstrainedTypeError::from(InvalidPattern {
-                field_name: "handle".to_string(),
-                expected: HANDLE_PATTERN.to_string(),
-                found: "-a".to_string(),
-            }).into()
-        );
+        assert_eq!(err.description(), Some(HANDLE_DESCRIPTION));
+        assert_eq!(err.rules(), rules);
+    }
 
-        assert_eq!(
-            new("handle", "a-", None),
-            ConstrainedTypeError::from(InvalidPattern {
-                field_name: "handle".to_string(),
-                expected: HANDLE_PATTERN.to_string(),
-                found: "a-".to_string(),
-            }).into()
-        );
+    #[test]
+    fn test_handle() {
+        assert_invalid_pattern("1", &["must start with a lower-case letter"]);
 
-        assert_eq!(
-            new("handle", "A", None),
-            ConstrainedTypeError::from(InvalidPattern {
-                field_name: "handle".to_string(),
-                expected: HANDLE_PATTERN.to_string(),
-                found: "A".to_string(),
-            }).into()
-        );
+        assert_invalid_pattern("1a", &["must start with a lower-case letter"]);
+
+        assert_invalid_pattern("-a", &["must start with a lower-case letter"]);
+
+        assert_invalid_pattern("a-", &["must not end with a hyphen"]);
+
+        assert_invalid_pattern("A", &["must start with a lower-case letter", "must only contain lower-case letters, numbers and hyphens"]);
 
         let mut handle = "aaaaaaaa".repeat(8);
-        handle.push_str("a");
+        handle.push('a');
+
+        assert_invalid_pattern(&handle, &["must not be longer than 64 characters"]);
 
         assert_eq!(
-            new("handle", &handle, None),
-            ConstrainedTypeError::from(InvalidPattern {
+            new("handle", "admin", None),
+            ConstrainedTypeError::from(Reserved {
                 field_name: "handle".to_string(),
-                expected: HANDLE_PATTERN.to_string(),
-                found: handle,
+                expected: "admin".to_string(),
+                found: "admin".to_string(),
             }).into()
         );
 
@@ -161,5 +171,11 @@ pub mod test {
         assert_eq!(format!("{:?}", new("handle", "a-1", None).unwrap()), "Handle(\"a-1\")");
 
         assert_eq!(format!("{}", new("handle", "a-1", None).unwrap()), "a-1");
+
+        assert_eq!(
+            new("handle", "A-", None).unwrap_err().to_string(),
+            "\"handle\" must start with a lower-case letter and must only contain lower-case letters, \
+             numbers and hyphens and must not end with a hyphen for value \"A-\""
+        );
     }
 }
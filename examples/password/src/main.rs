@@ -1,18 +1,19 @@
 use crate::password::{new};
-use structopt::StructOpt;
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser};
 
+/// A password is read once and cannot be cloned, so it is validated after parsing
+/// rather than by a value parser, but still reported as a usage error.
 fn main() {
-    let args = Cli::from_args();
+    let args = Cli::parse();
 
-    let res = new("password", &args.password, None);
-
-    match res {
+    match new("password", &args.password) {
         Ok(mut p) => { println!("{}", p.value().unwrap()); }
-        Err(_) => { println!("{:?}", res); }
+        Err(e) => { Cli::command().error(ErrorKind::ValueValidation, e).exit(); }
     }
 }
 
-#[derive(StructOpt)]
+#[derive(Parser)]
 struct Cli {
     pub password: String,
 }
@@ -44,117 +45,95 @@ struct Cli {
 ///
 mod password {
     use constrained_type::error::ConstrainedTypeResult;
-    use constrained_type::string_like::new_string_like;
+    use constrained_type::secret::{new_secret_string_like, Secret};
     use fancy_regex::Regex;
-    use zeroize::Zeroize;
 
     pub const PASSWORD_PATTERN: &str = r"^(?=.*[0-9])(?=.*[a-z])(?=.*[A-Z])(?=.*[*.!@$%\^\&(){}\[\]:;<>,.?\/~_+\-=|\\]).{8,32}$";
 
-    #[derive(Clone, PartialEq, Eq)]
-    pub struct Password(pub(crate) Option<String>);
+    #[derive(PartialEq)]
+    pub struct Password(pub(crate) Secret<String>);
 
     impl Password {
-        pub(crate) fn new<S: Into<String>>(raw: S) -> Password {
-            Self(Some(raw.into()))
+        pub(crate) fn new(secret: Secret<String>) -> Password {
+            Self(secret)
         }
 
         pub fn value(&mut self) -> Result<String, &str> {
-            match &self.0 {
-                Some(_) => {
-                    let value = self.0.as_ref().unwrap().to_string();
-                    self.0 = None;
-
-                    Ok(value)
-                }
-                None => Err("Password has already be consumed.")
-            }
-        }
-    }
-
-    impl Drop for Password {
-        fn drop(&mut self) {
-            let v = &mut self.0;
-
-            match v {
-                Some(_) => {
-                    v.zeroize();
-                }
-                _ => {}
-            }
+            self.0.consume().ok_or("Password has already be consumed.")
         }
     }
 
     impl std::fmt::Debug for Password {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            write!(f, "\"*******\"")
+            write!(f, "{:?}", self.0)
         }
     }
 
     impl std::fmt::Display for Password {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            write!(f, "*******")
+            write!(f, "{}", self.0)
         }
     }
 
-    pub fn new(field_name: &str, raw: &str, err_val: Option<&str>) -> ConstrainedTypeResult<Password> {
-        new_string_like(
+    pub fn new(field_name: &str, raw: &str) -> ConstrainedTypeResult<Password> {
+        new_secret_string_like(
             field_name,
-            |v| Password::new(v),
+            Password::new,
             Regex::new(PASSWORD_PATTERN).unwrap(),
             raw,
-            err_val,
         )
     }
 }
 
 #[cfg(test)]
 pub mod test {
-    use crate::password::{new, PASSWORD_PATTERN, Password};
+    use crate::password::{new, PASSWORD_PATTERN};
     use constrained_type::error::ConstrainedTypeErrorKind::InvalidPattern;
     use constrained_type::error::ConstrainedTypeError;
+    use constrained_type::secret::REDACTED;
 
     #[test]
     fn test_password() {
         assert_eq!(
-            new("password", "mypass", Some("<redacted>")),
+            new("password", "mypass").unwrap_err(),
             ConstrainedTypeError::from(InvalidPattern {
                 field_name: "password".to_string(),
                 expected: PASSWORD_PATTERN.to_string(),
-                found: "<redacted>".to_string(),
-            }).into()
+                found: REDACTED.to_string(),
+            })
         );
 
         assert_eq!(
-            new("password", "T3st^^^^", Some("<redacted>")),
-            Ok(Password(Some("T3st^^^^".to_string())))
+            new("password", "T3st^^^^").unwrap().value(),
+            Ok("T3st^^^^".to_string())
         );
 
         assert_eq!(
-            new("password", "T3st&&&&", Some("<redacted>")),
-            Ok(Password(Some("T3st&&&&".to_string())))
+            new("password", "T3st&&&&").unwrap().value(),
+            Ok("T3st&&&&".to_string())
         );
 
         assert_eq!(
-            new("password", "T3st[[[[", Some("<redacted>")),
-            Ok(Password(Some("T3st[[[[".to_string())))
+            new("password", "T3st[[[[").unwrap().value(),
+            Ok("T3st[[[[".to_string())
         );
 
         assert_eq!(
-            new("password", "T3st]]]]", Some("<redacted>")),
-            Ok(Password(Some("T3st]]]]".to_string())))
+            new("password", "T3st]]]]").unwrap().value(),
+            Ok("T3st]]]]".to_string())
         );
 
         assert_eq!(
-            new("password", "T3st////", Some("<redacted>")),
-            Ok(Password(Some("T3st////".to_string())))
+            new("password", "T3st////").unwrap().value(),
+            Ok("T3st////".to_string())
         );
 
         assert_eq!(
-            new("password", "T3st\\\\\\\\", Some("<redacted>")),
-            Ok(Password(Some("T3st\\\\\\\\".to_string())))
+            new("password", "T3st\\\\\\\\").unwrap().value(),
+            Ok("T3st\\\\\\\\".to_string())
         );
 
-        let mut pass = new("password", "T3st?^&[]", Some("<redacted>")).unwrap();
+        let mut pass = new("password", "T3st?^&[]").unwrap();
 
         let val = pass.value();
 
@@ -170,8 +149,12 @@ pub mod test {
             Err("Password has already be consumed.")
         );
 
-        assert_eq!(format!("{:?}", new("password", "T3st?^&[]", Some("<redacted>")).unwrap()), "\"*******\"");
+        assert_eq!(new("password", "T3st?^&[]").unwrap(), new("password", "T3st?^&[]").unwrap());
+
+        assert_ne!(new("password", "T3st?^&[]").unwrap(), new("password", "T3st?^&[[").unwrap());
+
+        assert_eq!(format!("{:?}", new("password", "T3st?^&[]").unwrap()), "\"*******\"");
 
-        assert_eq!(format!("{}", new("password", "T3st?^&[]", Some("<redacted>")).unwrap()), "*******");
+        assert_eq!(format!("{}", new("password", "T3st?^&[]").unwrap()), "*******");
     }
 }
\ No newline at end of file
@@ -0,0 +1,184 @@
+//! Reserved words and blocklists
+
+#![deny(missing_docs)]
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+use crate::error::ConstrainedTypeErrorKind::Reserved;
+use crate::error::{ConstrainedTypeError, ConstrainedTypeResult};
+
+/// How a value is matched against the terms of a blocklist
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchMode {
+    /// The value equals a term
+    Exact,
+    /// The value equals a term regardless of case
+    CaseInsensitive,
+    /// The value contains a term regardless of case
+    Substring,
+}
+
+/// A list of reserved or otherwise disallowed terms
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Blocklist {
+    terms: Vec<String>,
+    mode: MatchMode,
+}
+
+impl Blocklist {
+    /// Create a blocklist from the given terms, empty terms are ignored
+    pub fn new<I, S>(terms: I, mode: MatchMode) -> Self
+        where
+            I: IntoIterator<Item=S>,
+            S: Into<String>,
+    {
+        let terms = terms
+            .into_iter()
+            .map(|t| t.into())
+            .filter(|t: &String| !t.is_empty())
+            .map(|t| if mode == MatchMode::Exact { t } else { t.to_lowercase() })
+            .collect();
+
+        Self { terms, mode }
+    }
+
+    /// Read a blocklist with one term per line, blank lines and lines starting with `#` are skipped
+    pub fn from_reader<R: BufRead>(reader: R, mode: MatchMode) -> io::Result<Self> {
+        let mut terms = vec![];
+
+        for line in reader.lines() {
+            let line = line?;
+            let term = line.trim();
+
+            if !term.is_empty() && !term.starts_with('#') {
+                terms.push(term.to_string());
+            }
+        }
+
+        Ok(Self::new(terms, mode))
+    }
+
+    /// Read a blocklist file with one term per line
+    pub fn from_file<P: AsRef<Path>>(path: P, mode: MatchMode) -> io::Result<Self> {
+        Self::from_reader(BufReader::new(File::open(path)?), mode)
+    }
+
+    /// Get the first term the value matches
+    pub fn find(&self, val: &str) -> Option<&str> {
+        let val = match self.mode {
+            MatchMode::Exact => val.to_string(),
+            _ => val.to_lowercase(),
+        };
+
+        self.terms
+            .iter()
+            .find(|t| match self.mode {
+                MatchMode::Exact | MatchMode::CaseInsensitive => **t == val,
+                MatchMode::Substring => val.contains(t.as_str()),
+            })
+            .map(|t| t.as_str())
+    }
+}
+
+/// A builder function constraining a String to not match any term of a blocklist
+pub fn new_unreserved<'val, T, F>(
+    field_name: &str,
+    ctor: F,
+    blocklist: &Blocklist,
+    val: &'val str,
+) -> ConstrainedTypeResult<T>
+    where
+        F: Fn(&'val str) -> T,
+{
+    if let Some(term) = blocklist.find(val) {
+        return ConstrainedTypeError::from(Reserved {
+            field_name: field_name.to_string(),
+            expected: term.to_string(),
+            found: val.to_string(),
+        }).into();
+    }
+
+    Ok(ctor(val))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::blocklist::{Blocklist, MatchMode};
+    use crate::error::ConstrainedTypeError;
+    use crate::error::ConstrainedTypeErrorKind::{InvalidPattern, Reserved};
+
+    mod handle {
+        use crate::blocklist::{new_unreserved, Blocklist};
+        use crate::error::ConstrainedTypeResult;
+        use crate::string_like::new_string_like;
+        use fancy_regex::Regex;
+
+        pub(crate) const PATTERN: &str = r"^[a-z][a-z0-9\-]*$";
+
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct Handle(String);
+
+        impl Handle {
+            pub(crate) fn new<S: Into<String>>(raw: S) -> Handle {
+                Self(raw.into())
+            }
+
+            pub fn value(&self) -> &str {
+                &self.0
+            }
+        }
+
+        pub fn new(field_name: &str, raw: &str, blocklist: &Blocklist) -> ConstrainedTypeResult<Handle> {
+            new_string_like(field_name, |v| v, Regex::new(PATTERN).unwrap(), raw, None)
+                .and_then(|v| new_unreserved(field_name, Handle::new, blocklist, v))
+        }
+    }
+
+    const TERMS: &str = "# reserved\nadmin\n\nroot\nSupport\n";
+
+    #[test]
+    fn it_matches_terms_by_mode() {
+        let exact = Blocklist::from_reader(TERMS.as_bytes(), MatchMode::Exact).unwrap();
+        let case_insensitive = Blocklist::from_reader(TERMS.as_bytes(), MatchMode::CaseInsensitive).unwrap();
+        let substring = Blocklist::from_reader(TERMS.as_bytes(), MatchMode::Substring).unwrap();
+
+        assert_eq!(exact.find("admin"), Some("admin"));
+        assert_eq!(exact.find("support"), None);
+        assert_eq!(exact.find("# reserved"), None);
+        assert_eq!(case_insensitive.find("ROOT"), Some("root"));
+        assert_eq!(case_insensitive.find("root-1"), None);
+        assert_eq!(substring.find("the-Admin-team"), Some("admin"));
+    }
+
+    #[test]
+    fn it_errors_on_reserved_handle() {
+        let blocklist = Blocklist::new(vec!["admin", "root"], MatchMode::Substring);
+
+        assert_eq!(
+            handle::new("handle", "Admin", &blocklist),
+            ConstrainedTypeError::from(InvalidPattern {
+                field_name: "handle".to_string(),
+                expected: handle::PATTERN.to_string(),
+                found: "Admin".to_string(),
+            }).into()
+        );
+
+        assert_eq!(
+            handle::new("handle", "root-admin", &blocklist),
+            ConstrainedTypeError::from(Reserved {
+                field_name: "handle".to_string(),
+                expected: "admin".to_string(),
+                found: "root-admin".to_string(),
+            }).into()
+        );
+    }
+
+    #[test]
+    fn it_can_construct_an_unreserved_handle() {
+        let blocklist = Blocklist::new(vec!["admin", "root"], MatchMode::Exact);
+
+        assert_eq!(handle::new("handle", "rooted", &blocklist).unwrap().value(), "rooted");
+    }
+}
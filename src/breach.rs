@@ -0,0 +1,240 @@
+//! Breached password blocklist
+
+#![deny(missing_docs)]
+
+use std::fs::File;
+use std::io::{self, BufRead, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::sync::Mutex;
+
+use sha1::{Digest, Sha1};
+
+use crate::error::ConstrainedTypeErrorKind::{Breached, Unverifiable};
+use crate::error::{ConstrainedTypeError, ConstrainedTypeResult};
+
+const HASH_LEN: usize = 20;
+
+type Hash = [u8; HASH_LEN];
+
+/// An index of breached password SHA-1 hashes, looked up without any network calls
+///
+/// The index is a file of sorted, fixed-width binary hashes, searched in place, so only
+/// a single hash is held in memory at any time regardless of the size of the corpus.
+pub struct BreachIndex<R = File> {
+    reader: Mutex<R>,
+    len: u64,
+}
+
+impl BreachIndex<File> {
+    /// Open an index file previously written by [`BreachIndex::build`]
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::new(File::open(path)?)
+    }
+}
+
+impl<R: Read + Seek> BreachIndex<R> {
+    /// Use an index previously written by [`BreachIndex::build`]
+    pub fn new(mut reader: R) -> io::Result<Self> {
+        let size = reader.seek(SeekFrom::End(0))?;
+
+        if size % HASH_LEN as u64 != 0 {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "breach index is not a list of SHA-1 hashes"));
+        }
+
+        Ok(Self {
+            reader: Mutex::new(reader),
+            len: size / HASH_LEN as u64,
+        })
+    }
+
+    /// Get the number of hashes in the index
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Whether the index holds no hashes
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Whether the SHA-1 hash of the password is in the index
+    pub fn contains(&self, password: &str) -> io::Result<bool> {
+        let needle: Hash = Sha1::digest(password.as_bytes()).into();
+
+        let mut reader = self.reader.lock().unwrap_or_else(|e| e.into_inner());
+        let mut record: Hash = [0; HASH_LEN];
+        let (mut low, mut high) = (0, self.len);
+
+        while low < high {
+            let mid = low + (high - low) / 2;
+
+            reader.seek(SeekFrom::Start(mid * HASH_LEN as u64))?;
+            reader.read_exact(&mut record)?;
+
+            match record.cmp(&needle) {
+                std::cmp::Ordering::Less => low = mid + 1,
+                std::cmp::Ordering::Greater => high = mid,
+                std::cmp::Ordering::Equal => return Ok(true),
+            }
+        }
+
+        Ok(false)
+    }
+}
+
+impl BreachIndex {
+    /// Convert a hash list into an index, returning the number of hashes written
+    ///
+    /// Each line holds a hex encoded SHA-1 hash, optionally followed by `:` and a count, as in
+    /// the lists ordered by hash from Have I Been Pwned. The list is streamed, so it must already
+    /// be sorted by hash. Duplicate hashes are written once.
+    pub fn build<I: BufRead, W: Write>(reader: I, mut writer: W) -> io::Result<u64> {
+        let mut last: Option<Hash> = None;
+        let mut count = 0;
+
+        for line in reader.lines() {
+            let line = line?;
+            let hex = line.split(':').next().unwrap_or_default().trim();
+
+            if hex.is_empty() {
+                continue;
+            }
+
+            let hash = parse_hash(hex).ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidData, format!("invalid SHA-1 hash {:?}", hex))
+            })?;
+
+            match last {
+                Some(prev) if prev == hash => continue,
+                Some(prev) if prev > hash => {
+                    return Err(io::Error::new(io::ErrorKind::InvalidData, "hash list is not sorted by hash"));
+                }
+                _ => {}
+            }
+
+            writer.write_all(&hash)?;
+            last = Some(hash);
+            count += 1;
+        }
+
+        writer.flush()?;
+
+        Ok(count)
+    }
+}
+
+fn parse_hash(hex: &str) -> Option<Hash> {
+    if hex.len() != HASH_LEN * 2 || !hex.is_ascii() {
+        return None;
+    }
+
+    let mut hash: Hash = [0; HASH_LEN];
+
+    for (i, byte) in hash.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).ok()?;
+    }
+
+    Some(hash)
+}
+
+/// A builder function constraining a String to not appear in a breach index
+///
+/// The value is never part of the error.
+pub fn new_unbreached<'val, T, F, R>(
+    field_name: &str,
+    ctor: F,
+    index: &BreachIndex<R>,
+    val: &'val str,
+) -> ConstrainedTypeResult<T>
+    where
+        F: Fn(&'val str) -> T,
+        R: Read + Seek,
+{
+    match index.contains(val) {
+        Ok(false) => Ok(ctor(val)),
+        Ok(true) => ConstrainedTypeError::from(Breached {
+            field_name: field_name.to_string(),
+        }).into(),
+        Err(e) => ConstrainedTypeError::from(Unverifiable {
+            field_name: field_name.to_string(),
+            reason: e.to_string(),
+        }).into(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use crate::breach::BreachIndex;
+    use crate::error::ConstrainedTypeError;
+    use crate::error::ConstrainedTypeErrorKind::Breached;
+
+    // SHA-1 of "password", "123456" and "P@ssw0rd", ordered by hash
+    const HASH_LIST: &str = "21BD12DC183F740EE76F27B78EB39C8AD972A757:52579\n\
+                             5BAA61E4C9B93F3F0682250B6CF8331B7EE68FD8:9545824\n\
+                             5baa61e4c9b93f3f0682250b6cf8331b7ee68fd8:9545824\n\
+                             7C4A8D09CA3762AF61E59520943DC26494F8941B:37359195\n";
+
+    mod password {
+        use std::io::Cursor;
+
+        use crate::breach::{new_unbreached, BreachIndex};
+        use crate::error::ConstrainedTypeResult;
+
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct Password(String);
+
+        impl Password {
+            pub(crate) fn new<S: Into<String>>(raw: S) -> Password {
+                Self(raw.into())
+            }
+
+            pub fn value(&self) -> &str {
+                &self.0
+            }
+        }
+
+        pub fn new(field_name: &str, raw: &str, index: &BreachIndex<Cursor<Vec<u8>>>) -> ConstrainedTypeResult<Password> {
+            new_unbreached(field_name, Password::new, index, raw)
+        }
+    }
+
+    fn index() -> BreachIndex<Cursor<Vec<u8>>> {
+        let mut buf = vec![];
+
+        assert_eq!(BreachIndex::build(HASH_LIST.as_bytes(), &mut buf).unwrap(), 3);
+
+        BreachIndex::new(Cursor::new(buf)).unwrap()
+    }
+
+    #[test]
+    fn it_errors_on_breached_password() {
+        let index = index();
+
+        for breached in &["password", "123456", "P@ssw0rd"] {
+            assert_eq!(
+                password::new("password", breached, &index),
+                ConstrainedTypeError::from(Breached {
+                    field_name: "password".to_string(),
+                }).into()
+            );
+        }
+    }
+
+    #[test]
+    fn it_rejects_an_unsorted_hash_list() {
+        let unsorted = "7C4A8D09CA3762AF61E59520943DC26494F8941B\n21BD12DC183F740EE76F27B78EB39C8AD972A757\n";
+
+        assert!(BreachIndex::build(unsorted.as_bytes(), vec![]).is_err());
+        assert!(BreachIndex::new(Cursor::new(vec![0; 21])).is_err());
+    }
+
+    #[test]
+    fn it_can_construct_an_unbreached_password() {
+        assert_eq!(
+            password::new("password", "correct horse battery staple", &index()).unwrap().value(),
+            "correct horse battery staple"
+        );
+    }
+}
@@ -0,0 +1,239 @@
+//! Constrained character set
+
+#![deny(missing_docs)]
+
+pub use unicode_general_category::GeneralCategory;
+pub use unicode_script::Script;
+
+use unicode_general_category::get_general_category;
+use unicode_script::UnicodeScript;
+
+use crate::error::ConstrainedTypeErrorKind::InvalidChar;
+use crate::error::{ConstrainedTypeError, ConstrainedTypeResult, Span};
+
+/// The characters a String may contain, by Unicode general category and script
+///
+/// The default policy denies control, format, separator, private use, surrogate and unassigned
+/// code points, which covers NUL, bidi overrides and zero-width characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CharPolicy {
+    /// Categories every character must belong to, any category if empty
+    pub allowed_categories: Vec<GeneralCategory>,
+    /// Categories no character may belong to
+    pub denied_categories: Vec<GeneralCategory>,
+    /// Scripts every character must belong to, any script if empty
+    ///
+    /// Characters of the `Common` and `Inherited` scripts like digits, punctuation
+    /// and combining marks are allowed with any script.
+    pub allowed_scripts: Vec<Script>,
+    /// Scripts no character may belong to
+    pub denied_scripts: Vec<Script>,
+}
+
+impl Default for CharPolicy {
+    fn default() -> Self {
+        Self {
+            allowed_categories: vec![],
+            denied_categories: vec![
+                GeneralCategory::Control,
+                GeneralCategory::Format,
+                GeneralCategory::LineSeparator,
+                GeneralCategory::ParagraphSeparator,
+                GeneralCategory::PrivateUse,
+                GeneralCategory::Surrogate,
+                GeneralCategory::Unassigned,
+            ],
+            allowed_scripts: vec![],
+            denied_scripts: vec![],
+        }
+    }
+}
+
+impl CharPolicy {
+    /// Get the rule the character violates, `None` if it is allowed
+    fn violation(&self, c: char) -> Option<String> {
+        let category = get_general_category(c);
+
+        if self.denied_categories.contains(&category) {
+            return Some(format!("no {:?} characters", category));
+        }
+
+        if !self.allowed_categories.is_empty() && !self.allowed_categories.contains(&category) {
+            return Some(format!("{} characters", join(self.allowed_categories.iter().map(|c| format!("{:?}", c)))));
+        }
+
+        let script = c.script();
+
+        if self.denied_scripts.contains(&script) {
+            return Some(format!("no {} characters", script.full_name()));
+        }
+
+        if !self.allowed_scripts.is_empty()
+            && !self.allowed_scripts.contains(&script)
+            && script != Script::Common
+            && script != Script::Inherited {
+            return Some(format!("{} characters", join(self.allowed_scripts.iter().map(|s| s.full_name().to_string()))));
+        }
+
+        None
+    }
+}
+
+fn join<I: Iterator<Item=String>>(names: I) -> String {
+    names.collect::<Vec<_>>().join(" or ")
+}
+
+/// A builder function constraining every character of a String to satisfy a policy
+///
+/// The error reports the first offending code point and its character position.
+pub fn new_charset_string<'val, T, F>(
+    field_name: &str,
+    ctor: F,
+    policy: &CharPolicy,
+    val: &'val str,
+) -> ConstrainedTypeResult<T>
+    where
+        F: Fn(&'val str) -> T,
+{
+    for (position, (idx, c)) in val.char_indices().enumerate() {
+        if let Some(expected) = policy.violation(c) {
+            return ConstrainedTypeError::from(InvalidChar {
+                field_name: field_name.to_string(),
+                expected,
+                found: format!("U+{:04X}", c as u32),
+                position,
+            }).with_span(Span::new(val, idx, idx + c.len_utf8())).into();
+        }
+    }
+
+    Ok(ctor(val))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::charset::{CharPolicy, GeneralCategory, Script};
+    use crate::error::ConstrainedTypeErrorKind::InvalidChar;
+    use crate::error::{ConstrainedTypeError, Span};
+
+    mod comment {
+        use crate::charset::{new_charset_string, CharPolicy};
+        use crate::error::ConstrainedTypeResult;
+        use crate::string::new_string;
+
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct Comment(String);
+
+        impl Comment {
+            pub(crate) fn new<S: Into<String>>(raw: S) -> Comment {
+                Self(raw.into())
+            }
+
+            pub fn value(&self) -> &str {
+                &self.0
+            }
+        }
+
+        pub fn new(field_name: &str, raw: &str, policy: &CharPolicy) -> ConstrainedTypeResult<Comment> {
+            new_string(field_name, |v| v, 140, raw)
+                .and_then(|v| new_charset_string(field_name, Comment::new, policy, v))
+        }
+    }
+
+    #[test]
+    fn it_errors_on_control_bidi_and_invisible_characters() {
+        let policy = CharPolicy::default();
+
+        assert_eq!(
+            comment::new("comment", "a\u{0}b", &policy),
+            ConstrainedTypeError::from(InvalidChar {
+                field_name: "comment".to_string(),
+                expected: "no Control characters".to_string(),
+                found: "U+0000".to_string(),
+                position: 1,
+            }).into()
+        );
+
+        assert_eq!(
+            comment::new("comment", "a\u{0}b", &policy).unwrap_err().span(),
+            Some(Span { start: 1, end: 2, char_start: 1 })
+        );
+
+        assert_eq!(
+            comment::new("comment", "🐺 access\u{202E}", &policy),
+            ConstrainedTypeError::from(InvalidChar {
+                field_name: "comment".to_string(),
+                expected: "no Format characters".to_string(),
+                found: "U+202E".to_string(),
+                position: 8,
+            }).into()
+        );
+
+        assert_eq!(
+            comment::new("comment", "🐺 access\u{202E}", &policy).unwrap_err().span(),
+            Some(Span { start: 11, end: 14, char_start: 8 })
+        );
+
+        assert_eq!(
+            comment::new("comment", "ad\u{200B}min", &policy).unwrap_err().kind(),
+            &InvalidChar {
+                field_name: "comment".to_string(),
+                expected: "no Format characters".to_string(),
+                found: "U+200B".to_string(),
+                position: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn it_errors_on_characters_outside_allowed_scripts_and_categories() {
+        let policy = CharPolicy {
+            allowed_scripts: vec![Script::Latin],
+            ..CharPolicy::default()
+        };
+
+        assert_eq!(
+            comment::new("comment", "p\u{0430}ypal", &policy),
+            ConstrainedTypeError::from(InvalidChar {
+                field_name: "comment".to_string(),
+                expected: "Latin characters".to_string(),
+                found: "U+0430".to_string(),
+                position: 1,
+            }).into()
+        );
+
+        assert_eq!(
+            comment::new("comment", "p\u{0430}ypal", &policy).unwrap_err().span(),
+            Some(Span { start: 1, end: 3, char_start: 1 })
+        );
+
+        let policy = CharPolicy {
+            allowed_categories: vec![GeneralCategory::LowercaseLetter, GeneralCategory::DecimalNumber],
+            ..CharPolicy::default()
+        };
+
+        assert_eq!(
+            comment::new("comment", "abc 1", &policy),
+            ConstrainedTypeError::from(InvalidChar {
+                field_name: "comment".to_string(),
+                expected: "LowercaseLetter or DecimalNumber characters".to_string(),
+                found: "U+0020".to_string(),
+                position: 3,
+            }).into()
+        );
+
+        assert_eq!(
+            comment::new("comment", "abc 1", &policy).unwrap_err().span(),
+            Some(Span { start: 3, end: 4, char_start: 3 })
+        );
+    }
+
+    #[test]
+    fn it_can_construct_a_comment() {
+        let policy = CharPolicy {
+            allowed_scripts: vec![Script::Latin],
+            ..CharPolicy::default()
+        };
+
+        assert_eq!(comment::new("comment", "Café, 42 times!", &policy).unwrap().value(), "Café, 42 times!");
+    }
+}
@@ -0,0 +1,117 @@
+//! Command line argument parsers
+
+#![deny(missing_docs)]
+
+use std::ffi::OsStr;
+use std::fmt;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use clap::builder::TypedValueParser;
+use clap::error::ErrorKind;
+use clap::{Arg, Command, Error};
+
+use crate::validate::Validate;
+
+/// A clap value parser validating an argument into a constrained type
+///
+/// The argument id is used as field name, and a violated constraint is reported by clap
+/// as a usage error showing the constraint message.
+pub struct ConstrainedParser<T> {
+    marker: PhantomData<fn() -> T>,
+}
+
+/// Create a value parser validating an argument into `T`, e.g. `#[arg(value_parser = constrained::<Handle>())]`
+pub fn constrained<T>() -> ConstrainedParser<T> {
+    ConstrainedParser { marker: PhantomData }
+}
+
+impl<T> Clone for ConstrainedParser<T> {
+    fn clone(&self) -> Self {
+        constrained()
+    }
+}
+
+impl<T> fmt::Debug for ConstrainedParser<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ConstrainedParser<{}>", std::any::type_name::<T>())
+    }
+}
+
+impl<T> TypedValueParser for ConstrainedParser<T>
+    where
+        T: Validate + Clone + Send + Sync + 'static,
+        T::Raw: FromStr,
+        <T::Raw as FromStr>::Err: fmt::Display,
+{
+    type Value = T;
+
+    fn parse_ref(&self, cmd: &Command, arg: Option<&Arg>, value: &OsStr) -> Result<T, Error> {
+        let invalid = |message: String| match arg {
+            Some(arg) => cmd.clone().error(ErrorKind::ValueValidation, format!("invalid value for '{}': {}", arg, message)),
+            None => cmd.clone().error(ErrorKind::ValueValidation, message),
+        };
+
+        let value = value
+            .to_str()
+            .ok_or_else(|| cmd.clone().error(ErrorKind::InvalidUtf8, "invalid UTF-8 was detected in the argument"))?;
+        let raw = value.parse::<T::Raw>().map_err(|e| invalid(e.to_string()))?;
+
+        T::validate(arg.map_or("value", |a| a.get_id().as_str()), raw).map_err(|e| invalid(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::error::ErrorKind;
+    use clap::{Arg, Command};
+
+    use crate::cli::constrained;
+
+    mod port {
+        use crate::error::ConstrainedTypeResult;
+        use crate::int::new_int;
+        use crate::validate::Validate;
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub struct Port(u16);
+
+        impl Port {
+            pub fn value(&self) -> u16 {
+                self.0
+            }
+        }
+
+        impl Validate for Port {
+            type Raw = u16;
+
+            fn validate(field_name: &str, raw: u16) -> ConstrainedTypeResult<Port> {
+                new_int(field_name, Port, 1024, 49151, raw)
+            }
+        }
+    }
+
+    fn command() -> Command {
+        Command::new("serve").arg(Arg::new("port").long("port").value_parser(constrained::<port::Port>()))
+    }
+
+    #[test]
+    fn it_rejects_an_invalid_argument_with_the_constraint_message() {
+        let err = command().try_get_matches_from(["serve", "--port", "80"]).unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::ValueValidation);
+        assert!(err.to_string().contains("invalid value for '--port <port>': \"port\" must not be less than \"1024\", \"80\""));
+
+        let err = command().try_get_matches_from(["serve", "--port", "http"]).unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::ValueValidation);
+        assert!(err.to_string().contains("invalid value for '--port <port>': invalid digit found in string"));
+    }
+
+    #[test]
+    fn it_parses_a_valid_argument() {
+        let matches = command().try_get_matches_from(["serve", "--port", "8080"]).unwrap();
+
+        assert_eq!(matches.get_one::<port::Port>("port").unwrap().value(), 8080);
+    }
+}
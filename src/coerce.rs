@@ -0,0 +1,75 @@
+//! Coerced values and the report of what was adjusted
+
+#![deny(missing_docs)]
+
+/// What had to be changed for a value to fit its constraints
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Adjustment {
+    /// Number was raised to the minimum value
+    ClampedToMin {
+        /// Field name of the adjusted value
+        field_name: String,
+        /// Specified minimum value
+        expected: String,
+        /// Original value
+        found: String,
+    },
+    /// Number was lowered to the maximum value
+    ClampedToMax {
+        /// Field name of the adjusted value
+        field_name: String,
+        /// Specified maximum value
+        expected: String,
+        /// Original value
+        found: String,
+    },
+    /// Not-a-number was replaced by the minimum value
+    ReplacedNaN {
+        /// Field name of the adjusted value
+        field_name: String,
+        /// Specified minimum value
+        expected: String,
+    },
+    /// Character data was cut off at the character limit
+    Truncated {
+        /// Field name of the adjusted value
+        field_name: String,
+        /// Specified character limit
+        expected: String,
+        /// Original character count
+        found: String,
+    },
+}
+
+/// A constructed value together with the adjustment applied to the input, if any
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Coerced<T> {
+    value: T,
+    adjustment: Option<Adjustment>,
+}
+
+impl<T> Coerced<T> {
+    pub(crate) fn new(value: T, adjustment: Option<Adjustment>) -> Self {
+        Self { value, adjustment }
+    }
+
+    /// Get the constructed value
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Take the constructed value and discard the report
+    pub fn into_value(self) -> T {
+        self.value
+    }
+
+    /// Get the adjustment applied to the input, `None` if it was used unchanged
+    pub fn adjustment(&self) -> Option<&Adjustment> {
+        self.adjustment.as_ref()
+    }
+
+    /// Whether the input had to be adjusted
+    pub fn is_adjusted(&self) -> bool {
+        self.adjustment.is_some()
+    }
+}
@@ -0,0 +1,472 @@
+//! Constrained collections
+
+#![deny(missing_docs)]
+
+use core::fmt;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use crate::error::ConstrainedTypeErrorKind::{InvalidMaxItems, InvalidMinItems, InvalidUnique};
+use crate::error::{ConstrainedTypeError, ConstrainedTypeResult};
+
+/// A builder function constraining a Vec to a minimum and maximum item count,
+/// validating each item with the given builder
+///
+/// Items are passed to the builder with their index appended to the field name, e.g. `lines[3]`,
+/// so errors point at the failing item. If `unique` is set, a validated item must not equal
+/// any item before it. At most `max_len + 1` items are read from `vals`, so an oversized input
+/// is rejected without being collected.
+pub fn new_vec<T, F, B, E, V, I>(
+    field_name: &str,
+    ctor: F,
+    min_len: usize,
+    max_len: usize,
+    unique: bool,
+    builder: B,
+    vals: I,
+) -> ConstrainedTypeResult<T>
+    where
+        F: Fn(Vec<E>) -> T,
+        B: Fn(&str, V) -> ConstrainedTypeResult<E>,
+        E: PartialEq,
+        I: IntoIterator<Item=V>,
+{
+    let vals = take_bounded(field_name, min_len, max_len, vals)?;
+
+    let mut items: Vec<E> = Vec::with_capacity(vals.len());
+
+    for (idx, val) in vals.into_iter().enumerate() {
+        let item = builder(&item_field_name(field_name, idx), val)?;
+
+        if unique {
+            if let Some(first) = items.iter().position(|i| *i == item) {
+                return ConstrainedTypeError::from(InvalidUnique {
+                    field_name: item_field_name(field_name, idx),
+                    found: item_field_name(field_name, first),
+                }).into();
+            }
+        }
+
+        items.push(item);
+    }
+
+    Ok(ctor(items))
+}
+
+/// A builder function constraining a map to a minimum and maximum entry count,
+/// validating each key and value with the given builders
+///
+/// Keys and values are passed to the builders with the key appended to the field name,
+/// e.g. `metadata[env]`, so errors point at the offending entry. Keys that are equal after
+/// validation are rejected as duplicates. Pass a sorted map to get a deterministic first error.
+/// At most `max_len + 1` entries are read from `vals`, so an oversized input is rejected without
+/// being collected.
+pub fn new_map<T, F, KB, VB, K, V, EK, EV, I>(
+    field_name: &str,
+    ctor: F,
+    min_len: usize,
+    max_len: usize,
+    key_builder: KB,
+    value_builder: VB,
+    vals: I,
+) -> ConstrainedTypeResult<T>
+    where
+        F: Fn(HashMap<EK, EV>) -> T,
+        KB: Fn(&str, K) -> ConstrainedTypeResult<EK>,
+        VB: Fn(&str, V) -> ConstrainedTypeResult<EV>,
+        K: fmt::Display,
+        EK: Eq + Hash,
+        I: IntoIterator<Item=(K, V)>,
+{
+    let vals = take_bounded(field_name, min_len, max_len, vals)?;
+
+    let mut entries: HashMap<EK, (String, EV)> = HashMap::with_capacity(vals.len());
+
+    for (key, val) in vals {
+        let entry_field_name = item_field_name(field_name, &key);
+        let entry_key = key_builder(&entry_field_name, key)?;
+        let entry_val = value_builder(&entry_field_name, val)?;
+
+        if let Some((first, _)) = entries.get(&entry_key) {
+            return ConstrainedTypeError::from(InvalidUnique {
+                field_name: entry_field_name,
+                found: first.to_string(),
+            }).into();
+        }
+
+        entries.insert(entry_key, (entry_field_name, entry_val));
+    }
+
+    Ok(ctor(entries.into_iter().map(|(k, (_, v))| (k, v)).collect()))
+}
+
+/// A builder function constraining a set to a minimum and maximum item count,
+/// validating each item with the given builder
+///
+/// Items are passed to the builder with the item appended to the field name, e.g. `tags[beta]`.
+/// Items that are equal after validation are rejected as duplicates, like the keys of [`new_map`].
+/// At most `max_len + 1` items are read from `vals`, so an oversized input is rejected without
+/// being collected.
+pub fn new_set<T, F, B, E, V, I>(
+    field_name: &str,
+    ctor: F,
+    min_len: usize,
+    max_len: usize,
+    builder: B,
+    vals: I,
+) -> ConstrainedTypeResult<T>
+    where
+        F: Fn(HashSet<E>) -> T,
+        B: Fn(&str, V) -> ConstrainedTypeResult<E>,
+        V: fmt::Display,
+        E: Eq + Hash,
+        I: IntoIterator<Item=V>,
+{
+    let vals = take_bounded(field_name, min_len, max_len, vals)?;
+
+    let mut items: HashMap<E, String> = HashMap::with_capacity(vals.len());
+
+    for val in vals {
+        let item_field_name = item_field_name(field_name, &val);
+        let item = builder(&item_field_name, val)?;
+
+        if let Some(first) = items.get(&item) {
+            return ConstrainedTypeError::from(InvalidUnique {
+                field_name: item_field_name,
+                found: first.to_string(),
+            }).into();
+        }
+
+        items.insert(item, item_field_name);
+    }
+
+    Ok(ctor(items.into_keys().collect()))
+}
+
+/// Collect at most `max_len + 1` items and check their count
+///
+/// An oversized input is reported with its exact length if the iterator knows it, else as `max_len + 1`.
+pub(crate) fn take_bounded<I: IntoIterator>(
+    field_name: &str,
+    min_len: usize,
+    max_len: usize,
+    vals: I,
+) -> ConstrainedTypeResult<Vec<I::Item>> {
+    let vals = vals.into_iter();
+    let size_hint = vals.size_hint();
+    let vals: Vec<I::Item> = vals.take(max_len.saturating_add(1)).collect();
+
+    let len = match size_hint {
+        (lower, Some(upper)) if vals.len() > max_len && lower == upper => lower.max(vals.len()),
+        _ => vals.len(),
+    };
+
+    check_len(field_name, min_len, max_len, len)?;
+
+    Ok(vals)
+}
+
+pub(crate) fn check_len(
+    field_name: &str,
+    min_len: usize,
+    max_len: usize,
+    len: usize,
+) -> ConstrainedTypeResult<()> {
+    if len < min_len {
+        return ConstrainedTypeError::from(InvalidMinItems {
+            field_name: field_name.to_string(),
+            expected: min_len.to_string(),
+            found: len.to_string(),
+        }).into();
+    }
+
+    if len > max_len {
+        return ConstrainedTypeError::from(InvalidMaxItems {
+            field_name: field_name.to_string(),
+            expected: max_len.to_string(),
+            found: len.to_string(),
+        }).into();
+    }
+
+    Ok(())
+}
+
+pub(crate) fn item_field_name<I: fmt::Display>(field_name: &str, index: I) -> String {
+    format!("{}[{}]", field_name, index)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::{ConstrainedTypeError, Span};
+    use crate::error::ConstrainedTypeErrorKind::{
+        InvalidMaxItems, InvalidMaxLen, InvalidMinItems, InvalidMinVal, InvalidPattern, InvalidUnique,
+    };
+    use std::collections::BTreeMap;
+
+    use crate::collection::new_vec;
+    use crate::int::new_int;
+
+    mod order_lines {
+        use crate::collection::new_vec;
+        use crate::error::ConstrainedTypeResult;
+        use crate::int::new_int;
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub struct LineQuantity(u16);
+
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct OrderLines(Vec<LineQuantity>);
+
+        impl OrderLines {
+            const MIN_LEN: usize = 1;
+            const MAX_LEN: usize = 3;
+
+            pub(crate) fn new(lines: Vec<LineQuantity>) -> Self {
+                Self(lines)
+            }
+
+            pub fn len(&self) -> usize {
+                self.0.len()
+            }
+        }
+
+        pub fn new(field_name: &str, lines: &[u16], unique: bool) -> ConstrainedTypeResult<OrderLines> {
+            new_vec(
+                field_name,
+                OrderLines::new,
+                OrderLines::MIN_LEN,
+                OrderLines::MAX_LEN,
+                unique,
+                |field_name, qty| new_int(field_name, LineQuantity, 1, 50, *qty),
+                lines,
+            )
+        }
+    }
+
+    mod metadata {
+        use crate::collection::new_map;
+        use crate::error::ConstrainedTypeResult;
+        use crate::string::new_string;
+        use crate::string_like::new_string_like;
+        use fancy_regex::Regex;
+        use std::collections::HashMap;
+
+        pub(crate) const KEY_PATTERN: &str = r"^[a-z]+$";
+
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct Metadata(HashMap<String, String>);
+
+        impl Metadata {
+            const MAX_LEN: usize = 2;
+            const MAX_VALUE_LEN: usize = 5;
+
+            pub(crate) fn new(entries: HashMap<String, String>) -> Self {
+                Self(entries)
+            }
+
+            pub fn get(&self, key: &str) -> Option<&str> {
+                self.0.get(key).map(|v| v.as_str())
+            }
+        }
+
+        pub fn new<'a, I>(field_name: &str, entries: I) -> ConstrainedTypeResult<Metadata>
+            where
+                I: IntoIterator<Item=(&'a String, &'a String)>,
+        {
+            let key_pattern = Regex::new(KEY_PATTERN).unwrap();
+
+            new_map(
+                field_name,
+                Metadata::new,
+                0,
+                Metadata::MAX_LEN,
+                |field_name, key: &String| new_string_like(field_name, str::to_string, key_pattern.clone(), key, None),
+                |field_name, value: &String| new_string(field_name, str::to_string, Metadata::MAX_VALUE_LEN, value),
+                entries,
+            )
+        }
+    }
+
+    mod tags {
+        use crate::collection::new_set;
+        use crate::error::ConstrainedTypeResult;
+        use crate::string::new_string;
+        use std::collections::HashSet;
+
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct Tags(HashSet<String>);
+
+        impl Tags {
+            pub(crate) fn new(tags: HashSet<String>) -> Self {
+                Self(tags)
+            }
+
+            pub fn len(&self) -> usize {
+                self.0.len()
+            }
+        }
+
+        pub fn new(field_name: &str, tags: &[&str]) -> ConstrainedTypeResult<Tags> {
+            new_set(
+                field_name,
+                Tags::new,
+                1,
+                2,
+                |field_name, tag: &&str| new_string(field_name, str::to_lowercase, 5, tag),
+                tags,
+            )
+        }
+    }
+
+    #[test]
+    fn it_errors_on_out_of_bounds_item_count() {
+        assert_eq!(
+            order_lines::new("lines", &[], false),
+            ConstrainedTypeError::from(InvalidMinItems {
+                field_name: "lines".to_string(),
+                expected: (1).to_string(),
+                found: (0).to_string(),
+            }).into()
+        );
+
+        assert_eq!(
+            order_lines::new("lines", &[1, 2, 3, 4], false),
+            ConstrainedTypeError::from(InvalidMaxItems {
+                field_name: "lines".to_string(),
+                expected: (3).to_string(),
+                found: (4).to_string(),
+            }).into()
+        );
+    }
+
+    #[test]
+    fn it_stops_reading_an_oversized_input() {
+        let builder = |field_name: &str, qty: u16| new_int(field_name, |v| v, 1, 50, qty);
+
+        assert_eq!(
+            new_vec("lines", |v| v, 1, 3, false, builder, std::iter::repeat_n(1, 1_000_000)),
+            ConstrainedTypeError::from(InvalidMaxItems {
+                field_name: "lines".to_string(),
+                expected: (3).to_string(),
+                found: (1_000_000).to_string(),
+            }).into()
+        );
+
+        assert_eq!(
+            new_vec("lines", |v| v, 1, 3, false, builder, std::iter::repeat(1)),
+            ConstrainedTypeError::from(InvalidMaxItems {
+                field_name: "lines".to_string(),
+                expected: (3).to_string(),
+                found: (4).to_string(),
+            }).into()
+        );
+    }
+
+    #[test]
+    fn it_indexes_the_failing_item() {
+        assert_eq!(
+            order_lines::new("lines", &[1, 2, 0], false),
+            ConstrainedTypeError::from(InvalidMinVal {
+                field_name: "lines[2]".to_string(),
+                expected: (1).to_string(),
+                found: (0).to_string(),
+            }).into()
+        );
+
+        assert_eq!(
+            order_lines::new("lines", &[1, 2, 1], true),
+            ConstrainedTypeError::from(InvalidUnique {
+                field_name: "lines[2]".to_string(),
+                found: "lines[0]".to_string(),
+            }).into()
+        );
+    }
+
+    #[test]
+    fn it_can_construct_order_lines() {
+        assert_eq!(order_lines::new("lines", &[1, 2, 1], false).unwrap().len(), 3);
+        assert_eq!(order_lines::new("lines", &[1, 2, 3], true).unwrap().len(), 3);
+    }
+
+    fn entries(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn it_reports_map_errors_against_the_offending_key() {
+        assert_eq!(
+            metadata::new("metadata", &entries(&[("env", "prod"), ("Team", "core")])),
+            ConstrainedTypeError::from(InvalidPattern {
+                field_name: "metadata[Team]".to_string(),
+                expected: metadata::KEY_PATTERN.to_string(),
+                found: "Team".to_string(),
+            }).into()
+        );
+
+        assert_eq!(
+            metadata::new("metadata", &entries(&[("env", "production")])),
+            ConstrainedTypeError::from(InvalidMaxLen {
+                field_name: "metadata[env]".to_string(),
+                expected: (5).to_string(),
+                found: (10).to_string(),
+            }).into()
+        );
+
+        assert_eq!(
+            metadata::new("metadata", &entries(&[("env", "production")])).unwrap_err().span(),
+            Some(Span { start: 5, end: 10, char_start: 5 })
+        );
+
+        assert_eq!(
+            metadata::new("metadata", &entries(&[("a", "1"), ("b", "2"), ("c", "3")])),
+            ConstrainedTypeError::from(InvalidMaxItems {
+                field_name: "metadata".to_string(),
+                expected: (2).to_string(),
+                found: (3).to_string(),
+            }).into()
+        );
+    }
+
+    #[test]
+    fn it_can_construct_metadata() {
+        let metadata = metadata::new("metadata", &entries(&[("env", "prod")])).unwrap();
+
+        assert_eq!(metadata.get("env"), Some("prod"));
+    }
+
+    #[test]
+    fn it_rejects_equal_set_items() {
+        assert_eq!(
+            tags::new("tags", &["beta", "BETA"]),
+            ConstrainedTypeError::from(InvalidUnique {
+                field_name: "tags[BETA]".to_string(),
+                found: "tags[beta]".to_string(),
+            }).into()
+        );
+
+        assert_eq!(tags::new("tags", &["beta", "new"]).unwrap().len(), 2);
+
+        assert_eq!(
+            tags::new("tags", &["beta", "beta", "new"]),
+            ConstrainedTypeError::from(InvalidMaxItems {
+                field_name: "tags".to_string(),
+                expected: (2).to_string(),
+                found: (3).to_string(),
+            }).into()
+        );
+
+        assert_eq!(
+            tags::new("tags", &["beta", "popular"]),
+            ConstrainedTypeError::from(InvalidMaxLen {
+                field_name: "tags[popular]".to_string(),
+                expected: (5).to_string(),
+                found: (7).to_string(),
+            }).into()
+        );
+
+        assert_eq!(
+            tags::new("tags", &["beta", "popular"]).unwrap_err().span(),
+            Some(Span { start: 5, end: 7, char_start: 5 })
+        );
+    }
+}
@@ -0,0 +1,168 @@
+//! Unicode confusable and mixed-script detection
+
+#![deny(missing_docs)]
+
+use unicode_security::{skeleton as tr39_skeleton, MixedScript};
+
+use crate::error::ConstrainedTypeErrorKind::{Confusable, InvalidScript};
+use crate::error::{ConstrainedTypeError, ConstrainedTypeResult};
+
+/// Get the confusable skeleton of a String as defined by Unicode TR39
+///
+/// Two Strings with the same skeleton look alike, e.g. `paypal` and `pаypal` with a Cyrillic `а`.
+pub fn skeleton(val: &str) -> String {
+    tr39_skeleton(val).collect()
+}
+
+/// A set of names others must not be confusable with, e.g. brand or staff names
+///
+/// Names are matched both as written and regardless of case, so `PayPal` protects
+/// `paypal` as well as `paypaI` with an upper-case `I`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProtectedNames {
+    names: Vec<(String, String, String)>,
+}
+
+impl ProtectedNames {
+    /// Create a set of protected names, computing their skeletons once
+    pub fn new<I, S>(names: I) -> Self
+        where
+            I: IntoIterator<Item=S>,
+            S: Into<String>,
+    {
+        let names = names
+            .into_iter()
+            .map(|n| {
+                let name = n.into();
+                let exact = skeleton(&name);
+                let lowercase = skeleton(&name.to_lowercase());
+                (name, exact, lowercase)
+            })
+            .collect();
+
+        Self { names }
+    }
+
+    /// Get the first protected name the value is confusable with, including the name itself
+    pub fn find(&self, val: &str) -> Option<&str> {
+        let (exact, lowercase) = (skeleton(val), skeleton(&val.to_lowercase()));
+
+        self.names
+            .iter()
+            .find(|(_, e, l)| *e == exact || *l == lowercase)
+            .map(|(n, _, _)| n.as_str())
+    }
+}
+
+/// A builder function constraining a String to be written in a single script
+///
+/// Characters shared by several scripts, like digits or punctuation, do not count as mixing,
+/// and neither do scripts commonly written together, like Han and Hiragana.
+pub fn new_single_script<'val, T, F>(
+    field_name: &str,
+    ctor: F,
+    val: &'val str,
+) -> ConstrainedTypeResult<T>
+    where
+        F: Fn(&'val str) -> T,
+{
+    if !val.is_single_script() {
+        return ConstrainedTypeError::from(InvalidScript {
+            field_name: field_name.to_string(),
+            found: val.to_string(),
+        }).into();
+    }
+
+    Ok(ctor(val))
+}
+
+/// A builder function constraining a String to not be confusable with any protected name
+pub fn new_unconfusable<'val, T, F>(
+    field_name: &str,
+    ctor: F,
+    protected: &ProtectedNames,
+    val: &'val str,
+) -> ConstrainedTypeResult<T>
+    where
+        F: Fn(&'val str) -> T,
+{
+    if let Some(name) = protected.find(val) {
+        return ConstrainedTypeError::from(Confusable {
+            field_name: field_name.to_string(),
+            expected: name.to_string(),
+            found: val.to_string(),
+        }).into();
+    }
+
+    Ok(ctor(val))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::confusable::{skeleton, ProtectedNames};
+    use crate::error::ConstrainedTypeError;
+    use crate::error::ConstrainedTypeErrorKind::{Confusable, InvalidScript};
+
+    mod display_name {
+        use crate::confusable::{new_single_script, new_unconfusable, ProtectedNames};
+        use crate::error::ConstrainedTypeResult;
+
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct DisplayName(String);
+
+        impl DisplayName {
+            pub(crate) fn new<S: Into<String>>(raw: S) -> DisplayName {
+                Self(raw.into())
+            }
+
+            pub fn value(&self) -> &str {
+                &self.0
+            }
+        }
+
+        pub fn new(field_name: &str, raw: &str, protected: &ProtectedNames) -> ConstrainedTypeResult<DisplayName> {
+            new_single_script(field_name, |v| v, raw)
+                .and_then(|v| new_unconfusable(field_name, DisplayName::new, protected, v))
+        }
+    }
+
+    #[test]
+    fn it_detects_confusable_skeletons() {
+        assert_eq!(skeleton("p\u{0430}ypal"), skeleton("paypal"));
+        assert_ne!(skeleton("PAYPAL"), skeleton("paypal"));
+        assert_eq!(skeleton("paypa1"), skeleton("paypal"));
+        assert_ne!(skeleton("paypa2"), skeleton("paypal"));
+        assert_eq!(ProtectedNames::new(vec!["PayPal"]).find("paypal"), Some("PayPal"));
+        assert_eq!(ProtectedNames::new(vec!["paypal"]).find("paypaI"), Some("paypal"));
+    }
+
+    #[test]
+    fn it_errors_on_mixed_script_or_confusable_name() {
+        let protected = ProtectedNames::new(vec!["paypal", "scope"]);
+
+        assert_eq!(
+            display_name::new("display_name", "p\u{0430}ypal", &protected),
+            ConstrainedTypeError::from(InvalidScript {
+                field_name: "display_name".to_string(),
+                found: "p\u{0430}ypal".to_string(),
+            }).into()
+        );
+
+        assert_eq!(
+            display_name::new("display_name", "\u{0455}\u{0441}\u{043e}\u{0440}\u{0435}", &protected),
+            ConstrainedTypeError::from(Confusable {
+                field_name: "display_name".to_string(),
+                expected: "scope".to_string(),
+                found: "\u{0455}\u{0441}\u{043e}\u{0440}\u{0435}".to_string(),
+            }).into()
+        );
+    }
+
+    #[test]
+    fn it_can_construct_a_display_name() {
+        let protected = ProtectedNames::new(vec!["paypal"]);
+
+        assert_eq!(display_name::new("display_name", "Jane Doe 42", &protected).unwrap().value(), "Jane Doe 42");
+        assert_eq!(display_name::new("display_name", "Иван", &protected).unwrap().value(), "Иван");
+    }
+}
@@ -0,0 +1,330 @@
+//! Constraint metadata
+
+#![deny(missing_docs)]
+
+use core::fmt;
+
+use num_traits::{Float, PrimInt};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::string_like::Pattern;
+
+/// A constrained type describing the constraints it is built with, e.g. for form generators and docs
+///
+/// Implementations should pass the same bounds as their builder function, e.g.
+///
+/// ```ignore
+/// impl Constraints for Quantity {
+///     fn constraints() -> ConstraintSet {
+///         ConstraintSet::int(MIN_QTY, MAX_QTY)
+///     }
+/// }
+/// ```
+pub trait Constraints {
+    /// The constraints of the type
+    fn constraints() -> ConstraintSet;
+}
+
+/// The type of the value a constrained type wraps
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(rename_all = "snake_case"))]
+pub enum BaseType {
+    /// An integer, see [`new_int`](crate::int::new_int)
+    Integer,
+    /// A floating point number, see [`new_float`](crate::float::new_float)
+    Float,
+    /// A String, see [`new_string`](crate::string::new_string)
+    String,
+}
+
+/// What a length bound counts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(rename_all = "snake_case"))]
+#[non_exhaustive]
+pub enum LengthUnit {
+    /// Unicode scalar values as counted by `str::chars`, not bytes or UTF-16 code units
+    Characters,
+}
+
+/// A minimum or maximum value, serialized as a plain number
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(untagged))]
+pub enum Bound {
+    /// A signed integer
+    Int(i64),
+    /// An unsigned integer above `i64::MAX`
+    UInt(u64),
+    /// A floating point number
+    Float(f64),
+}
+
+impl Bound {
+    fn from_int<V: PrimInt>(val: V) -> Self {
+        val.to_i64()
+            .map(Bound::Int)
+            .or_else(|| val.to_u64().map(Bound::UInt))
+            .unwrap_or_else(|| Bound::Float(val.to_f64().unwrap_or(f64::NAN)))
+    }
+
+    /// Whether the bound is a finite number, infinity and NaN are no bound in other languages
+    pub fn is_finite(&self) -> bool {
+        match self {
+            Bound::Float(val) => val.is_finite(),
+            _ => true,
+        }
+    }
+}
+
+impl fmt::Display for Bound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Bound::Int(val) => write!(f, "{}", val),
+            Bound::UInt(val) => write!(f, "{}", val),
+            Bound::Float(val) => write!(f, "{}", val),
+        }
+    }
+}
+
+/// The constraints of a constrained type as data, e.g. to generate database or API schemas
+///
+/// Build it from the same bounds passed to the builder functions, so both stay in lockstep.
+/// New constraints are added as fields, so create it with the constructors.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub struct ConstraintSet {
+    /// Type of the wrapped value
+    pub base_type: BaseType,
+    /// Minimum value
+    pub min: Option<Bound>,
+    /// Maximum value
+    pub max: Option<Bound>,
+    /// Minimum number of characters
+    pub min_len: Option<usize>,
+    /// Maximum number of characters
+    pub max_len: Option<usize>,
+    /// What the length bounds count
+    pub length_unit: Option<LengthUnit>,
+    /// Regex the value must match
+    pub pattern: Option<String>,
+    /// Human description of the value
+    pub description: Option<String>,
+    /// Whether the value may be absent
+    pub optional: bool,
+    /// Whether the value is a secret and must not be shown
+    pub sensitive: bool,
+}
+
+impl ConstraintSet {
+    fn new(base_type: BaseType) -> Self {
+        Self {
+            base_type,
+            min: None,
+            max: None,
+            min_len: None,
+            max_len: None,
+            length_unit: None,
+            pattern: None,
+            description: None,
+            optional: false,
+            sensitive: false,
+        }
+    }
+
+    /// The constraints of [`new_int`](crate::int::new_int)
+    ///
+    /// 128-bit bounds outside of the 64-bit range are approximated as floating point numbers.
+    pub fn int<V: PrimInt>(min_val: V, max_val: V) -> Self {
+        Self {
+            min: Some(Bound::from_int(min_val)),
+            max: Some(Bound::from_int(max_val)),
+            ..Self::new(BaseType::Integer)
+        }
+    }
+
+    /// The constraints of [`new_float`](crate::float::new_float)
+    ///
+    /// Infinite bounds do not constrain the value, so they are left out.
+    pub fn float<V: Float>(min_val: V, max_val: V) -> Self {
+        let bound = |val: V| val.to_f64().map(Bound::Float).filter(Bound::is_finite);
+
+        Self {
+            min: bound(min_val),
+            max: bound(max_val),
+            ..Self::new(BaseType::Float)
+        }
+    }
+
+    /// The constraints of [`new_string`](crate::string::new_string)
+    pub fn string(max_len: usize) -> Self {
+        Self {
+            min_len: Some(1),
+            max_len: Some(max_len),
+            length_unit: Some(LengthUnit::Characters),
+            ..Self::new(BaseType::String)
+        }
+    }
+
+    /// The constraints of [`new_string_option`](crate::string_option::new_string_option)
+    pub fn string_option(max_len: usize) -> Self {
+        Self {
+            max_len: Some(max_len),
+            length_unit: Some(LengthUnit::Characters),
+            optional: true,
+            ..Self::new(BaseType::String)
+        }
+    }
+
+    /// The constraints of [`new_string_like`](crate::string_like::new_string_like)
+    pub fn string_like(pattern: &Pattern) -> Self {
+        Self::new(BaseType::String).with_pattern(pattern)
+    }
+
+    /// Add the regex and description of a pattern, its rules are not included
+    pub fn with_pattern(mut self, pattern: &Pattern) -> Self {
+        self.pattern = Some(pattern.regex().as_str().to_string());
+        self.description = pattern.description().map(str::to_string).or(self.description);
+        self
+    }
+
+    /// Describe the value
+    pub fn with_description<S: Into<String>>(mut self, description: S) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Mark the value as secret, see [`new_secret_string`](crate::secret::new_secret_string)
+    pub fn secret(mut self) -> Self {
+        self.sensitive = true;
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use fancy_regex::Regex;
+
+    use crate::constraint::{BaseType, Bound, ConstraintSet, Constraints, LengthUnit};
+    use crate::string_like::Pattern;
+
+    mod api_key {
+        use fancy_regex::Regex;
+
+        use crate::constraint::{ConstraintSet, Constraints};
+        use crate::error::ConstrainedTypeResult;
+        use crate::secret::{new_secret_string_like, Secret};
+        use crate::string_like::Pattern;
+
+        pub struct ApiKey(Secret<String>);
+
+        impl ApiKey {
+            pub fn value(&mut self) -> Option<String> {
+                self.0.consume()
+            }
+        }
+
+        fn pattern() -> Pattern {
+            Pattern::new(Regex::new(r"^key_[a-z0-9]{32}$").unwrap()).with_description("an API key")
+        }
+
+        pub fn new(field_name: &str, raw: &str) -> ConstrainedTypeResult<ApiKey> {
+            new_secret_string_like(field_name, ApiKey, pattern(), raw)
+        }
+
+        impl Constraints for ApiKey {
+            fn constraints() -> ConstraintSet {
+                ConstraintSet::string_like(&pattern()).secret()
+            }
+        }
+    }
+
+    #[test]
+    fn it_combines_the_constraints_of_builders() {
+        let pattern = Pattern::new(Regex::new(r"^[a-z]+$").unwrap()).with_description("a lower-case name");
+
+        assert_eq!(
+            ConstraintSet::string(63).with_pattern(&pattern),
+            ConstraintSet {
+                base_type: BaseType::String,
+                min: None,
+                max: None,
+                min_len: Some(1),
+                max_len: Some(63),
+                length_unit: Some(LengthUnit::Characters),
+                pattern: Some(r"^[a-z]+$".to_string()),
+                description: Some("a lower-case name".to_string()),
+                optional: false,
+                sensitive: false,
+            }
+        );
+
+        let qty = ConstraintSet::int(1u16, 1000);
+
+        assert_eq!((qty.min, qty.max), (Some(Bound::Int(1)), Some(Bound::Int(1000))));
+
+        let id = ConstraintSet::int(1u64, u64::MAX);
+
+        assert_eq!((id.min, id.max), (Some(Bound::Int(1)), Some(Bound::UInt(u64::MAX))));
+
+        let ratio = ConstraintSet::float(f64::NEG_INFINITY, 0.5);
+
+        assert_eq!((ratio.min, ratio.max), (None, Some(Bound::Float(0.5))));
+    }
+
+    #[test]
+    fn it_describes_a_constrained_type() {
+        let mut key = api_key::new("api_key", "key_0123456789abcdef0123456789abcdef").unwrap();
+
+        assert_eq!(key.value().as_deref(), Some("key_0123456789abcdef0123456789abcdef"));
+
+        let constraints = api_key::ApiKey::constraints();
+
+        assert_eq!(constraints.pattern.as_deref(), Some(r"^key_[a-z0-9]{32}$"));
+        assert_eq!(constraints.description.as_deref(), Some("an API key"));
+        assert!(constraints.sensitive);
+        assert!(!constraints.optional);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn it_serializes_the_constraints() {
+        use serde_json::json;
+
+        assert_eq!(
+            serde_json::to_value(ConstraintSet::string_option(280)).unwrap(),
+            json!({
+                "base_type": "string",
+                "min": null,
+                "max": null,
+                "min_len": null,
+                "max_len": 280,
+                "length_unit": "characters",
+                "pattern": null,
+                "description": null,
+                "optional": true,
+                "sensitive": false,
+            })
+        );
+
+        assert_eq!(
+            serde_json::to_value(ConstraintSet::float(-1.5, 1e3)).unwrap()["min"],
+            json!(-1.5)
+        );
+
+        let qty = ConstraintSet::int(-1i32, 1000);
+
+        assert_eq!(
+            (serde_json::to_value(&qty).unwrap()["min"].clone(), serde_json::to_value(&qty).unwrap()["max"].clone()),
+            (json!(-1), json!(1000))
+        );
+        assert_eq!(serde_json::from_value::<ConstraintSet>(serde_json::to_value(&qty).unwrap()).unwrap(), qty);
+
+        let constraints = api_key::ApiKey::constraints();
+
+        assert_eq!(
+            serde_json::from_str::<ConstraintSet>(&serde_json::to_string(&constraints).unwrap()).unwrap(),
+            constraints
+        );
+    }
+}
@@ -0,0 +1,166 @@
+//! Database column mapping
+
+#![deny(missing_docs)]
+
+#[doc(hidden)]
+pub mod __private {
+    pub use sqlx::encode::IsNull;
+    pub use sqlx::error::BoxDynError;
+    pub use sqlx::{Database, Decode, Encode, Type};
+
+    pub use crate::validate::Validate;
+}
+
+/// Implement sqlx `Type`, `Encode` and `Decode` for a constrained type through its raw representation
+///
+/// The type must implement [`Validate`](crate::validate::Validate), and its `Raw` representation
+/// must be convertible from a reference to the type. Decoding re-runs the validation with the given
+/// field name, so a row violating a constraint fails with the `ConstrainedTypeError` as decode error
+/// instead of silently becoming a valid value.
+///
+/// The field name is fixed per type, not taken from the column, so a type stored in several
+/// columns reports the same field name for a corrupt row in any of them, e.g. `handle` for
+/// both `author_handle` and `reviewer_handle`. Use a wrapper type per column to tell them apart.
+///
+/// ```ignore
+/// impl_sqlx_type!(Handle, "handle");
+/// ```
+#[macro_export]
+macro_rules! impl_sqlx_type {
+    ($ty:ty, $field_name:expr) => {
+        impl<DB> $crate::database::__private::Type<DB> for $ty
+            where
+                DB: $crate::database::__private::Database,
+                <$ty as $crate::database::__private::Validate>::Raw: $crate::database::__private::Type<DB>,
+        {
+            fn type_info() -> DB::TypeInfo {
+                <<$ty as $crate::database::__private::Validate>::Raw as $crate::database::__private::Type<DB>>::type_info()
+            }
+
+            fn compatible(ty: &DB::TypeInfo) -> bool {
+                <<$ty as $crate::database::__private::Validate>::Raw as $crate::database::__private::Type<DB>>::compatible(ty)
+            }
+        }
+
+        impl<'q, DB> $crate::database::__private::Encode<'q, DB> for $ty
+            where
+                DB: $crate::database::__private::Database,
+                <$ty as $crate::database::__private::Validate>::Raw: $crate::database::__private::Encode<'q, DB>,
+                <$ty as $crate::database::__private::Validate>::Raw: for<'a> From<&'a $ty>,
+        {
+            fn encode_by_ref(
+                &self,
+                buf: &mut <DB as $crate::database::__private::Database>::ArgumentBuffer<'q>,
+            ) -> Result<$crate::database::__private::IsNull, $crate::database::__private::BoxDynError> {
+                let raw = <<$ty as $crate::database::__private::Validate>::Raw as From<&$ty>>::from(self);
+
+                $crate::database::__private::Encode::<'q, DB>::encode(raw, buf)
+            }
+        }
+
+        impl<'r, DB> $crate::database::__private::Decode<'r, DB> for $ty
+            where
+                DB: $crate::database::__private::Database,
+                <$ty as $crate::database::__private::Validate>::Raw: $crate::database::__private::Decode<'r, DB>,
+        {
+            fn decode(
+                value: <DB as $crate::database::__private::Database>::ValueRef<'r>,
+            ) -> Result<Self, $crate::database::__private::BoxDynError> {
+                let raw = <<$ty as $crate::database::__private::Validate>::Raw as $crate::database::__private::Decode<'r, DB>>::decode(value)?;
+
+                Ok(<$ty as $crate::database::__private::Validate>::validate($field_name, raw)?)
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use sqlx::sqlite::SqlitePool;
+
+    use crate::error::ConstrainedTypeError;
+    use crate::error::ConstrainedTypeErrorKind::InvalidPattern;
+
+    mod handle {
+        use fancy_regex::Regex;
+
+        use crate::error::ConstrainedTypeResult;
+        use crate::string_like::new_string_like;
+        use crate::validate::Validate;
+
+        pub(crate) const PATTERN: &str = r"^[a-z][a-z0-9\-]*$";
+
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct Handle(String);
+
+        impl Handle {
+            pub(crate) fn new<S: Into<String>>(raw: S) -> Handle {
+                Self(raw.into())
+            }
+
+            pub fn value(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl Validate for Handle {
+            type Raw = String;
+
+            fn validate(field_name: &str, raw: String) -> ConstrainedTypeResult<Handle> {
+                new_string_like(field_name, Handle::new, Regex::new(PATTERN).unwrap(), &raw, None)
+            }
+        }
+
+        impl From<&Handle> for String {
+            fn from(handle: &Handle) -> String {
+                handle.value().to_string()
+            }
+        }
+
+        crate::impl_sqlx_type!(Handle, "handle");
+    }
+
+    async fn pool() -> SqlitePool {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+
+        sqlx::query("CREATE TABLE users (handle TEXT NOT NULL)").execute(&pool).await.unwrap();
+
+        pool
+    }
+
+    #[tokio::test]
+    async fn it_encodes_and_decodes_a_valid_handle() {
+        let pool = pool().await;
+        let handle = handle::Handle::new("jane-doe");
+
+        sqlx::query("INSERT INTO users (handle) VALUES (?)").bind(&handle).execute(&pool).await.unwrap();
+
+        let loaded: handle::Handle = sqlx::query_scalar("SELECT handle FROM users").fetch_one(&pool).await.unwrap();
+
+        assert_eq!(loaded, handle);
+    }
+
+    #[tokio::test]
+    async fn it_errors_on_decoding_a_corrupt_handle() {
+        let pool = pool().await;
+
+        sqlx::query("INSERT INTO users (handle) VALUES ('Jane Doe')").execute(&pool).await.unwrap();
+
+        let err = sqlx::query_scalar::<_, handle::Handle>("SELECT handle FROM users")
+            .fetch_one(&pool)
+            .await
+            .unwrap_err();
+
+        match err {
+            sqlx::Error::ColumnDecode { source, .. } => assert_eq!(
+                source.downcast_ref::<ConstrainedTypeError>().map(|e| e.kind()),
+                Some(&InvalidPattern {
+                    field_name: "handle".to_string(),
+                    expected: handle::PATTERN.to_string(),
+                    found: "Jane Doe".to_string(),
+                })
+            ),
+            err => panic!("expected a column decode error, got {:?}", err),
+        }
+    }
+}
@@ -0,0 +1,380 @@
+//! Constrained date and date time
+
+#![deny(missing_docs)]
+
+use core::fmt;
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc, Weekday};
+
+use crate::error::ConstrainedTypeErrorKind::{InvalidMaxVal, InvalidMinVal, InvalidTime, InvalidWeekday};
+use crate::error::{ConstrainedTypeError, ConstrainedTypeResult};
+
+/// A source of the current date time, injected to resolve bounds relative to now
+pub trait Clock {
+    /// Get the current date time
+    fn now(&self) -> NaiveDateTime;
+}
+
+/// A clock reading the current system time in UTC
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> NaiveDateTime {
+        Utc::now().naive_utc()
+    }
+}
+
+/// A clock always returning the same date time, e.g. for tests
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedClock(pub NaiveDateTime);
+
+impl Clock for FixedClock {
+    fn now(&self) -> NaiveDateTime {
+        self.0
+    }
+}
+
+/// A lower or upper limit for a date or date time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bound<V> {
+    /// A fixed point in time
+    At(V),
+    /// An offset from the current date time of the clock, negative for the past
+    FromNow(Duration),
+}
+
+impl Bound<NaiveDate> {
+    fn resolve<C: Clock>(&self, clock: &C) -> NaiveDate {
+        match self {
+            Bound::At(v) => *v,
+            Bound::FromNow(offset) => (clock.now() + *offset).date(),
+        }
+    }
+}
+
+impl Bound<NaiveDateTime> {
+    fn resolve<C: Clock>(&self, clock: &C) -> NaiveDateTime {
+        match self {
+            Bound::At(v) => *v,
+            Bound::FromNow(offset) => clock.now() + *offset,
+        }
+    }
+}
+
+/// A rule a date has to satisfy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DateRule {
+    /// The date must not be before the bound
+    NotBefore(Bound<NaiveDate>),
+    /// The date must not be after the bound
+    NotAfter(Bound<NaiveDate>),
+    /// The date must fall on one of the days of the week
+    OnWeekdays(Vec<Weekday>),
+}
+
+/// A rule a date time has to satisfy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DateTimeRule {
+    /// The date time must not be before the bound
+    NotBefore(Bound<NaiveDateTime>),
+    /// The date time must not be after the bound
+    NotAfter(Bound<NaiveDateTime>),
+    /// The date time must fall on one of the days of the week
+    OnWeekdays(Vec<Weekday>),
+    /// The time of day must be at or after the start and before the end,
+    /// wrapping around midnight if the start is after the end
+    ///
+    /// An equal start and end wraps around to the same time of the next day, so every time
+    /// of day is within.
+    WithinHours(NaiveTime, NaiveTime),
+}
+
+/// A builder function constraining a date by the given rules
+pub fn new_date<T, F, C>(
+    field_name: &str,
+    ctor: F,
+    clock: &C,
+    rules: &[DateRule],
+    val: NaiveDate,
+) -> ConstrainedTypeResult<T>
+    where
+        F: Fn(NaiveDate) -> T,
+        C: Clock,
+{
+    for rule in rules {
+        match rule {
+            DateRule::NotBefore(bound) => check_min(field_name, bound.resolve(clock), val)?,
+            DateRule::NotAfter(bound) => check_max(field_name, bound.resolve(clock), val)?,
+            DateRule::OnWeekdays(weekdays) => check_weekday(field_name, weekdays, val.weekday())?,
+        }
+    }
+
+    Ok(ctor(val))
+}
+
+/// A builder function constraining a date time by the given rules
+pub fn new_date_time<T, F, C>(
+    field_name: &str,
+    ctor: F,
+    clock: &C,
+    rules: &[DateTimeRule],
+    val: NaiveDateTime,
+) -> ConstrainedTypeResult<T>
+    where
+        F: Fn(NaiveDateTime) -> T,
+        C: Clock,
+{
+    for rule in rules {
+        match rule {
+            DateTimeRule::NotBefore(bound) => check_min(field_name, bound.resolve(clock), val)?,
+            DateTimeRule::NotAfter(bound) => check_max(field_name, bound.resolve(clock), val)?,
+            DateTimeRule::OnWeekdays(weekdays) => check_weekday(field_name, weekdays, val.weekday())?,
+            DateTimeRule::WithinHours(start, end) => check_time(field_name, *start, *end, val.time())?,
+        }
+    }
+
+    Ok(ctor(val))
+}
+
+fn check_min<V: PartialOrd + fmt::Display>(field_name: &str, min_val: V, val: V) -> ConstrainedTypeResult<()> {
+    if val < min_val {
+        return ConstrainedTypeError::from(InvalidMinVal {
+            field_name: field_name.to_string(),
+            expected: min_val.to_string(),
+            found: val.to_string(),
+        }).into();
+    }
+
+    Ok(())
+}
+
+fn check_max<V: PartialOrd + fmt::Display>(field_name: &str, max_val: V, val: V) -> ConstrainedTypeResult<()> {
+    if val > max_val {
+        return ConstrainedTypeError::from(InvalidMaxVal {
+            field_name: field_name.to_string(),
+            expected: max_val.to_string(),
+            found: val.to_string(),
+        }).into();
+    }
+
+    Ok(())
+}
+
+fn check_weekday(field_name: &str, weekdays: &[Weekday], val: Weekday) -> ConstrainedTypeResult<()> {
+    if !weekdays.contains(&val) {
+        return ConstrainedTypeError::from(InvalidWeekday {
+            field_name: field_name.to_string(),
+            expected: weekdays.iter().map(|d| d.to_string()).collect::<Vec<_>>().join(", "),
+            found: val.to_string(),
+        }).into();
+    }
+
+    Ok(())
+}
+
+fn check_time(field_name: &str, start: NaiveTime, end: NaiveTime, val: NaiveTime) -> ConstrainedTypeResult<()> {
+    let within = if start == end {
+        true
+    } else if start < end {
+        start <= val && val < end
+    } else {
+        start <= val || val < end
+    };
+
+    if !within {
+        return ConstrainedTypeError::from(InvalidTime {
+            field_name: field_name.to_string(),
+            expected: format!("{}-{}", start, end),
+            found: val.to_string(),
+        }).into();
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+
+    use crate::datetime::{new_date_time, DateTimeRule, FixedClock};
+    use crate::error::ConstrainedTypeError;
+    use crate::error::ConstrainedTypeErrorKind::{InvalidMaxVal, InvalidMinVal, InvalidTime, InvalidWeekday};
+
+    mod birth_date {
+        use chrono::{Duration, NaiveDate};
+
+        use crate::datetime::{new_date, Bound, Clock, DateRule};
+        use crate::error::ConstrainedTypeResult;
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub struct BirthDate(NaiveDate);
+
+        impl BirthDate {
+            pub(crate) const fn new(value: NaiveDate) -> Self {
+                Self(value)
+            }
+
+            pub const fn value(&self) -> NaiveDate {
+                self.0
+            }
+        }
+
+        pub fn new<C: Clock>(field_name: &str, clock: &C, value: NaiveDate) -> ConstrainedTypeResult<BirthDate> {
+            new_date(
+                field_name,
+                BirthDate::new,
+                clock,
+                &[
+                    DateRule::NotBefore(Bound::At(NaiveDate::from_ymd_opt(1900, 1, 1).unwrap())),
+                    DateRule::NotAfter(Bound::FromNow(Duration::zero())),
+                ],
+                value,
+            )
+        }
+    }
+
+    mod appointment {
+        use chrono::{Duration, NaiveDateTime, NaiveTime, Weekday};
+
+        use crate::datetime::{new_date_time, Bound, Clock, DateTimeRule};
+        use crate::error::ConstrainedTypeResult;
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub struct Appointment(NaiveDateTime);
+
+        impl Appointment {
+            pub(crate) const fn new(value: NaiveDateTime) -> Self {
+                Self(value)
+            }
+
+            pub const fn value(&self) -> NaiveDateTime {
+                self.0
+            }
+        }
+
+        pub fn new<C: Clock>(field_name: &str, clock: &C, value: NaiveDateTime) -> ConstrainedTypeResult<Appointment> {
+            new_date_time(
+                field_name,
+                Appointment::new,
+                clock,
+                &[
+                    DateTimeRule::NotBefore(Bound::FromNow(Duration::zero())),
+                    DateTimeRule::NotAfter(Bound::FromNow(Duration::days(90))),
+                    DateTimeRule::OnWeekdays(vec![Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri]),
+                    DateTimeRule::WithinHours(
+                        NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+                        NaiveTime::from_hms_opt(17, 0, 0).unwrap(),
+                    ),
+                ],
+                value,
+            )
+        }
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn date_time(y: i32, m: u32, d: u32, h: u32) -> NaiveDateTime {
+        date(y, m, d).and_hms_opt(h, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn it_errors_on_out_of_bounds_birth_date() {
+        let clock = FixedClock(date_time(2021, 8, 16, 12));
+
+        assert_eq!(
+            birth_date::new("birth_date", &clock, date(1899, 12, 31)),
+            ConstrainedTypeError::from(InvalidMinVal {
+                field_name: "birth_date".to_string(),
+                expected: "1900-01-01".to_string(),
+                found: "1899-12-31".to_string(),
+            }).into()
+        );
+
+        assert_eq!(
+            birth_date::new("birth_date", &clock, date(2021, 8, 17)),
+            ConstrainedTypeError::from(InvalidMaxVal {
+                field_name: "birth_date".to_string(),
+                expected: "2021-08-16".to_string(),
+                found: "2021-08-17".to_string(),
+            }).into()
+        );
+    }
+
+    #[test]
+    fn it_can_construct_a_birth_date() {
+        let clock = FixedClock(date_time(2021, 8, 16, 12));
+
+        assert_eq!(
+            birth_date::new("birth_date", &clock, date(2021, 8, 16)).unwrap().value(),
+            date(2021, 8, 16)
+        );
+    }
+
+    #[test]
+    fn it_errors_on_invalid_appointment() {
+        let clock = FixedClock(date_time(2021, 8, 16, 12));
+
+        assert_eq!(
+            appointment::new("appointment", &clock, date_time(2021, 8, 16, 11)),
+            ConstrainedTypeError::from(InvalidMinVal {
+                field_name: "appointment".to_string(),
+                expected: "2021-08-16 12:00:00".to_string(),
+                found: "2021-08-16 11:00:00".to_string(),
+            }).into()
+        );
+
+        assert_eq!(
+            appointment::new("appointment", &clock, date_time(2021, 11, 15, 10)),
+            ConstrainedTypeError::from(InvalidMaxVal {
+                field_name: "appointment".to_string(),
+                expected: "2021-11-14 12:00:00".to_string(),
+                found: "2021-11-15 10:00:00".to_string(),
+            }).into()
+        );
+
+        assert_eq!(
+            appointment::new("appointment", &clock, date_time(2021, 8, 21, 10)),
+            ConstrainedTypeError::from(InvalidWeekday {
+                field_name: "appointment".to_string(),
+                expected: "Mon, Tue, Wed, Thu, Fri".to_string(),
+                found: "Sat".to_string(),
+            }).into()
+        );
+
+        assert_eq!(
+            appointment::new("appointment", &clock, date_time(2021, 8, 17, 17)),
+            ConstrainedTypeError::from(InvalidTime {
+                field_name: "appointment".to_string(),
+                expected: "09:00:00-17:00:00".to_string(),
+                found: "17:00:00".to_string(),
+            }).into()
+        );
+    }
+
+    #[test]
+    fn it_accepts_any_time_within_equal_hours() {
+        let clock = FixedClock(date_time(2021, 8, 16, 12));
+        let nine = NaiveTime::from_hms_opt(9, 0, 0).unwrap();
+        let rules = [DateTimeRule::WithinHours(nine, nine)];
+
+        for hour in [0, 8, 9, 10, 23] {
+            assert_eq!(
+                new_date_time("shift", |v| v, &clock, &rules, date_time(2021, 8, 17, hour)),
+                Ok(date_time(2021, 8, 17, hour))
+            );
+        }
+    }
+
+    #[test]
+    fn it_can_construct_an_appointment() {
+        let clock = FixedClock(date_time(2021, 8, 16, 12));
+
+        assert_eq!(
+            appointment::new("appointment", &clock, date_time(2021, 8, 17, 9)).unwrap().value(),
+            date_time(2021, 8, 17, 9)
+        );
+    }
+}
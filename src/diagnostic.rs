@@ -0,0 +1,178 @@
+//! Pretty diagnostics
+
+#![deny(missing_docs)]
+
+use std::fmt::Display;
+
+use miette::{Diagnostic, LabeledSpan, SourceCode};
+
+use crate::error::ConstrainedTypeErrorKind::{self, *};
+use crate::error::ConstrainedTypeError;
+
+/// Reports the error with a `constrained_type::` code, a help text and, given the input
+/// with [`ConstrainedTypeError::with_input`], a label over the offending span
+///
+/// Without an input, the value reported by a pattern, reserved term or confusable name error
+/// is labeled instead, unless the error is [`sensitive`](ConstrainedTypeError::sensitive).
+impl Diagnostic for ConstrainedTypeError {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        Some(Box::new(format!("constrained_type::{}", self.kind().code())))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        help(self).map(|h| Box::new(h) as Box<dyn Display>)
+    }
+
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        source(self).map(|s| s as &dyn SourceCode)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item=LabeledSpan> + '_>> {
+        let len = source(self)?.len();
+
+        let labels: Vec<LabeledSpan> = self
+            .errors()
+            .into_iter()
+            .map(|e| match e.span() {
+                Some(span) => LabeledSpan::at(span.start..span.end, label(e.kind())),
+                None => LabeledSpan::at(0..len, label(e.kind())),
+            })
+            .collect();
+
+        Some(Box::new(labels.into_iter()))
+    }
+
+    fn related<'a>(&'a self) -> Option<Box<dyn Iterator<Item=&'a dyn Diagnostic> + 'a>> {
+        match self.kind() {
+            Multiple { errors } => Some(Box::new(errors.iter().map(|e| e as &dyn Diagnostic))),
+            _ => None,
+        }
+    }
+}
+
+fn source(err: &ConstrainedTypeError) -> Option<&String> {
+    match err.kind() {
+        _ if err.input_source().is_some() => err.input_source(),
+        _ if err.is_sensitive() => None,
+        InvalidPattern { found, .. }
+        | Reserved { found, .. }
+        | InvalidScript { found, .. }
+        | Confusable { found, .. } => Some(found),
+        _ => None,
+    }
+}
+
+fn label(kind: &ConstrainedTypeErrorKind) -> &'static str {
+    match kind {
+        InvalidMaxLen { .. } => "exceeds the limit",
+        InvalidRepetition { .. } => "repeated character",
+        InvalidChar { .. } => "invalid character",
+        _ => "invalid value",
+    }
+}
+
+fn help(err: &ConstrainedTypeError) -> Option<String> {
+    let help = match err.kind() {
+        InvalidMaxVal { expected, .. } => format!("use a value of at most {}", expected),
+        InvalidMinVal { expected, .. } => format!("use a value of at least {}", expected),
+        InvalidPattern { .. } if err.description().is_some() => format!("use {}", err.description().unwrap()),
+        InvalidPattern { expected, .. } => format!("use a value matching {}", expected),
+        InvalidOption { .. } => "provide a value".to_string(),
+        InvalidMaxLen { expected, .. } => format!("shorten the value to at most {} characters", expected),
+        InvalidMinLen { expected, .. } => format!("lengthen the value to at least {} characters", expected),
+        InvalidMinItems { expected, .. } => format!("provide at least {} items", expected),
+        InvalidMaxItems { expected, .. } => format!("provide at most {} items", expected),
+        InvalidUnique { found, .. } => format!("remove the duplicate {}", found),
+        InvalidWeekday { expected, .. } => format!("pick a day on {}", expected),
+        InvalidTime { expected, .. } => format!("pick a time within {}", expected),
+        InvalidCharClass { expected, .. } => format!("add at least one {}", expected),
+        InvalidRepetition { expected, .. } => format!("repeat a character at most {} times in a row", expected),
+        InvalidSubstring { found, .. } => format!("remove {:?}", found),
+        WeakPassword { feedback, .. } if !feedback.is_empty() => feedback.join(" "),
+        WeakPassword { .. } => "choose a less guessable password".to_string(),
+        Breached { .. } => "choose a password that has not appeared in a data breach".to_string(),
+        Reserved { expected, .. } => format!("choose a value other than {:?}", expected),
+        InvalidScript { .. } => "use characters of a single script".to_string(),
+        Confusable { expected, .. } => format!("choose a value that does not look like {:?}", expected),
+        InvalidChar { found, .. } => format!("remove the character {}", found),
+        Unverifiable { .. } | InvalidHash { .. } | Consumed { .. } | Multiple { .. } => return None,
+    };
+
+    Some(help)
+}
+
+#[cfg(test)]
+mod test {
+    use miette::{Diagnostic, LabeledSpan};
+
+    use crate::error::ConstrainedTypeError;
+    use crate::error::ConstrainedTypeErrorKind::InvalidPattern;
+
+    mod name {
+        use crate::charset::{new_charset_string, CharPolicy};
+        use crate::error::ConstrainedTypeResult;
+        use crate::string::new_string;
+
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct Name(String);
+
+        impl Name {
+            pub(crate) fn new<S: Into<String>>(raw: S) -> Name {
+                Self(raw.into())
+            }
+        }
+
+        pub fn new(field_name: &str, raw: &str) -> ConstrainedTypeResult<Name> {
+            new_string(field_name, |v| v, 5, raw)
+                .and_then(|v| new_charset_string(field_name, Name::new, &CharPolicy::default(), v))
+                .map_err(|e| e.with_input(raw))
+        }
+    }
+
+    #[test]
+    fn it_labels_the_offending_span_of_the_input() {
+        let err = name::new("name", "ab\u{202E}c").unwrap_err();
+
+        assert_eq!(err.code().unwrap().to_string(), "constrained_type::invalid_char");
+        assert_eq!(err.help().unwrap().to_string(), "remove the character U+202E");
+        assert!(err.source_code().is_some());
+        assert_eq!(
+            err.labels().unwrap().collect::<Vec<_>>(),
+            vec![LabeledSpan::at(2..5, "invalid character")]
+        );
+
+        let err = name::new("name", "abcdefg").unwrap_err();
+
+        assert_eq!(err.code().unwrap().to_string(), "constrained_type::invalid_max_len");
+        assert_eq!(err.labels().unwrap().collect::<Vec<_>>(), vec![LabeledSpan::at(5..7, "exceeds the limit")]);
+    }
+
+    #[test]
+    fn it_labels_the_reported_value_without_input() {
+        let err = ConstrainedTypeError::from(InvalidPattern {
+            field_name: "handle".to_string(),
+            expected: "^[a-z]+$".to_string(),
+            found: "Admin".to_string(),
+        });
+
+        assert_eq!(err.help().unwrap().to_string(), "use a value matching ^[a-z]+$");
+        assert_eq!(err.labels().unwrap().collect::<Vec<_>>(), vec![LabeledSpan::at(0..5, "invalid value")]);
+    }
+
+    #[test]
+    fn it_never_labels_the_value_of_a_sensitive_error() {
+        let err = ConstrainedTypeError::from(InvalidPattern {
+            field_name: "api_key".to_string(),
+            expected: "^key_[a-z]+$".to_string(),
+            found: "key_SECRET".to_string(),
+        }).sensitive();
+
+        assert!(err.source_code().is_none());
+        assert!(err.labels().is_none());
+
+        let err = err.with_input("key_SECRET");
+
+        assert!(err.source_code().is_some());
+        assert_eq!(err.labels().unwrap().collect::<Vec<_>>(), vec![LabeledSpan::at(0..10, "invalid value")]);
+    }
+}
@@ -6,11 +6,16 @@ use std::fmt;
 
 use thiserror::Error;
 
+use crate::secret::REDACTED;
+
 /// An alias for results returned by functions of this crate
 pub type ConstrainedTypeResult<T> = ::std::result::Result<T, ConstrainedTypeError>;
 
 /// The concrete error kind
-#[derive(Error, Debug, Eq, PartialEq)]
+///
+/// New kinds are added as constraints are, so match it with a wildcard arm.
+#[derive(Error, Debug, Clone, Eq, PartialEq)]
+#[non_exhaustive]
 pub enum ConstrainedTypeErrorKind {
     /// Number exceeded the maximum value
     #[error("{field_name:?} must not be greater than {expected:?}, {found:?}")]
@@ -58,24 +63,470 @@ pub enum ConstrainedTypeErrorKind {
         /// Actual value
         found: String,
     },
+    /// Collection holds fewer items than required
+    #[error("{field_name:?} must not have less than {expected:?} items, {found:?}")]
+    InvalidMinItems {
+        /// Field name shown in the error
+        field_name: String,
+        /// Specified minimum item count
+        expected: String,
+        /// Actual item count
+        found: String,
+    },
+    /// Collection holds more items than allowed
+    #[error("{field_name:?} must not have more than {expected:?} items, {found:?}")]
+    InvalidMaxItems {
+        /// Field name shown in the error
+        field_name: String,
+        /// Specified maximum item count
+        expected: String,
+        /// Actual item count
+        found: String,
+    },
+    /// Collection item is not unique
+    #[error("{field_name:?} must be unique, duplicate of {found:?}")]
+    InvalidUnique {
+        /// Field name of the duplicate item shown in the error
+        field_name: String,
+        /// Field name of the item it duplicates
+        found: String,
+    },
+    /// Date falls on a day of the week that is not allowed
+    #[error("{field_name:?} must be on {expected:?}, {found:?}")]
+    InvalidWeekday {
+        /// Field name shown in the error
+        field_name: String,
+        /// Allowed days of the week
+        expected: String,
+        /// Actual day of the week
+        found: String,
+    },
+    /// Time of day is outside the allowed hours
+    #[error("{field_name:?} must be within {expected:?}, {found:?}")]
+    InvalidTime {
+        /// Field name shown in the error
+        field_name: String,
+        /// Allowed time range
+        expected: String,
+        /// Actual time of day
+        found: String,
+    },
+    /// Character data is shorter than the limit
+    #[error("{field_name:?} must not be less than {expected:?} characters, {found:?}")]
+    InvalidMinLen {
+        /// Field name shown in the error
+        field_name: String,
+        /// Specified character limit
+        expected: String,
+        /// Actual value
+        found: String,
+    },
+    /// String is missing a required class of characters
+    #[error("{field_name:?} must contain at least one {expected}")]
+    InvalidCharClass {
+        /// Field name shown in the error
+        field_name: String,
+        /// Required class of characters
+        expected: String,
+    },
+    /// String repeats a character too many times in a row
+    #[error("{field_name:?} must not repeat a character more than {expected:?} times in a row, {found:?}")]
+    InvalidRepetition {
+        /// Field name shown in the error
+        field_name: String,
+        /// Specified repetition limit
+        expected: String,
+        /// Actual longest repetition
+        found: String,
+    },
+    /// String contains a disallowed term
+    #[error("{field_name:?} must not contain {found:?}")]
+    InvalidSubstring {
+        /// Field name shown in the error
+        field_name: String,
+        /// Disallowed term found in the value
+        found: String,
+    },
+    /// Password is too easy to guess
+    #[error("{field_name:?} must have a strength score of at least {expected:?}, {found:?}")]
+    WeakPassword {
+        /// Field name shown in the error
+        field_name: String,
+        /// Specified minimum score
+        expected: String,
+        /// Actual score
+        found: String,
+        /// Hints on how to choose a stronger password
+        feedback: Vec<String>,
+    },
+    /// String appears in a list of breached passwords
+    #[error("{field_name:?} appears in a data breach")]
+    Breached {
+        /// Field name shown in the error
+        field_name: String,
+    },
+    /// Value could not be checked against a constraint
+    #[error("{field_name:?} could not be verified, {reason:?}")]
+    Unverifiable {
+        /// Field name shown in the error
+        field_name: String,
+        /// Why the check failed
+        reason: String,
+    },
+    /// String is not a valid password hash
+    #[error("{field_name:?} is not a valid password hash, {reason:?}")]
+    InvalidHash {
+        /// Field name shown in the error
+        field_name: String,
+        /// Why the hash is invalid
+        reason: String,
+    },
+    /// Secret was already consumed
+    #[error("{field_name:?} has already been consumed")]
+    Consumed {
+        /// Field name shown in the error
+        field_name: String,
+    },
+    /// String matches a reserved or blocked term
+    #[error("{field_name:?} must not match the reserved term {expected:?}, {found:?}")]
+    Reserved {
+        /// Field name shown in the error
+        field_name: String,
+        /// Matched term
+        expected: String,
+        /// Actual value
+        found: String,
+    },
+    /// String mixes characters of different scripts
+    #[error("{field_name:?} must not mix scripts, {found:?}")]
+    InvalidScript {
+        /// Field name shown in the error
+        field_name: String,
+        /// Actual value
+        found: String,
+    },
+    /// String looks like a protected name
+    #[error("{field_name:?} must not be confusable with {expected:?}, {found:?}")]
+    Confusable {
+        /// Field name shown in the error
+        field_name: String,
+        /// Protected name it is confusable with
+        expected: String,
+        /// Actual value
+        found: String,
+    },
+    /// String contains a character that is not allowed
+    #[error("{field_name:?} must contain {expected}, {found:?} at position {position}")]
+    InvalidChar {
+        /// Field name shown in the error
+        field_name: String,
+        /// Violated character rule
+        expected: String,
+        /// Offending code point
+        found: String,
+        /// Character position of the offending code point
+        position: usize,
+    },
+    /// Several constraints failed at once
+    #[error("{}", join(.errors))]
+    Multiple {
+        /// The individual errors
+        errors: Vec<ConstrainedTypeError>,
+    },
+}
+
+impl ConstrainedTypeErrorKind {
+    fn found_mut(&mut self) -> Option<&mut String> {
+        use ConstrainedTypeErrorKind::*;
+
+        match self {
+            InvalidMaxVal { found, .. }
+            | InvalidMinVal { found, .. }
+            | InvalidPattern { found, .. }
+            | InvalidMaxLen { found, .. }
+            | InvalidMinItems { found, .. }
+            | InvalidMaxItems { found, .. }
+            | InvalidUnique { found, .. }
+            | InvalidWeekday { found, .. }
+            | InvalidTime { found, .. }
+            | InvalidMinLen { found, .. }
+            | InvalidRepetition { found, .. }
+            | InvalidSubstring { found, .. }
+            | WeakPassword { found, .. }
+            | Reserved { found, .. }
+            | InvalidScript { found, .. }
+            | Confusable { found, .. }
+            | InvalidChar { found, .. } => Some(found),
+            InvalidOption { .. }
+            | InvalidCharClass { .. }
+            | Breached { .. }
+            | Unverifiable { .. }
+            | InvalidHash { .. }
+            | Consumed { .. }
+            | Multiple { .. } => None,
+        }
+    }
+
+    /// Get a stable, machine-readable code of the error kind, e.g. `invalid_max_len`
+    pub fn code(&self) -> &'static str {
+        use ConstrainedTypeErrorKind::*;
+
+        match self {
+            InvalidMaxVal { .. } => "invalid_max_val",
+            InvalidMinVal { .. } => "invalid_min_val",
+            InvalidPattern { .. } => "invalid_pattern",
+            InvalidOption { .. } => "invalid_option",
+            InvalidMaxLen { .. } => "invalid_max_len",
+            InvalidMinItems { .. } => "invalid_min_items",
+            InvalidMaxItems { .. } => "invalid_max_items",
+            InvalidUnique { .. } => "invalid_unique",
+            InvalidWeekday { .. } => "invalid_weekday",
+            InvalidTime { .. } => "invalid_time",
+            InvalidMinLen { .. } => "invalid_min_len",
+            InvalidCharClass { .. } => "invalid_char_class",
+            InvalidRepetition { .. } => "invalid_repetition",
+            InvalidSubstring { .. } => "invalid_substring",
+            WeakPassword { .. } => "weak_password",
+            Breached { .. } => "breached",
+            Unverifiable { .. } => "unverifiable",
+            InvalidHash { .. } => "invalid_hash",
+            Consumed { .. } => "consumed",
+            Reserved { .. } => "reserved",
+            InvalidScript { .. } => "invalid_script",
+            Confusable { .. } => "confusable",
+            InvalidChar { .. } => "invalid_char",
+            Multiple { .. } => "multiple",
+        }
+    }
+
+    /// Get the field name the error is reported against, `None` for several errors
+    pub fn field_name(&self) -> Option<&str> {
+        use ConstrainedTypeErrorKind::*;
+
+        match self {
+            InvalidMaxVal { field_name, .. }
+            | InvalidMinVal { field_name, .. }
+            | InvalidPattern { field_name, .. }
+            | InvalidOption { field_name, .. }
+            | InvalidMaxLen { field_name, .. }
+            | InvalidMinItems { field_name, .. }
+            | InvalidMaxItems { field_name, .. }
+            | InvalidUnique { field_name, .. }
+            | InvalidWeekday { field_name, .. }
+            | InvalidTime { field_name, .. }
+            | InvalidMinLen { field_name, .. }
+            | InvalidCharClass { field_name, .. }
+            | InvalidRepetition { field_name, .. }
+            | InvalidSubstring { field_name, .. }
+            | WeakPassword { field_name, .. }
+            | Breached { field_name, .. }
+            | Unverifiable { field_name, .. }
+            | InvalidHash { field_name, .. }
+            | Consumed { field_name, .. }
+            | Reserved { field_name, .. }
+            | InvalidScript { field_name, .. }
+            | Confusable { field_name, .. }
+            | InvalidChar { field_name, .. } => Some(field_name),
+            Multiple { .. } => None,
+        }
+    }
 }
 
 /// The error type for errors that get returned in the crate
-#[derive(Error, Debug, Eq, PartialEq)]
+///
+/// Errors compare equal by their kind, the span and input are not compared.
+#[derive(Error, Debug, Clone)]
 pub struct ConstrainedTypeError {
     kind: ConstrainedTypeErrorKind,
+    context: Option<Box<Context>>,
+}
+
+/// Details located in the value, boxed to keep the error small
+#[derive(Debug, Clone, Default)]
+struct Context {
+    span: Option<Span>,
+    input: Option<String>,
+    description: Option<String>,
+    rules: Vec<String>,
+    sensitive: bool,
+}
+
+/// The part of a String value where a constraint was first violated, e.g. to highlight it
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Span {
+    /// Byte offset of the first offending character
+    pub start: usize,
+    /// Byte offset after the last offending character
+    pub end: usize,
+    /// Character offset of the first offending character
+    pub char_start: usize,
+}
+
+impl Span {
+    /// Create a span from a byte range of the value, which must lie on character boundaries
+    pub fn new(val: &str, start: usize, end: usize) -> Span {
+        Span {
+            start,
+            end,
+            char_start: val[..start].chars().count(),
+        }
+    }
+
+    /// Create a span from the given character offset up to the end of the value
+    pub fn from_char(val: &str, char_start: usize) -> Span {
+        let start = val.char_indices().nth(char_start).map_or(val.len(), |(i, _)| i);
+
+        Span {
+            start,
+            end: val.len(),
+            char_start,
+        }
+    }
+
+    /// Get the number of offending bytes
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Whether the span covers no bytes
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
 }
 
 impl ConstrainedTypeError {
+    /// Combine several errors into one, keeping a single error as it is
+    pub fn multiple(mut errors: Vec<ConstrainedTypeError>) -> ConstrainedTypeError {
+        if errors.len() == 1 {
+            return errors.remove(0);
+        }
+
+        ConstrainedTypeError::from(ConstrainedTypeErrorKind::Multiple { errors })
+    }
+
     /// Get the kind of the error
     pub fn kind(&self) -> &ConstrainedTypeErrorKind {
         &self.kind
     }
+
+    /// Attach the span of the value where the constraint was first violated
+    pub fn with_span(mut self, span: Span) -> ConstrainedTypeError {
+        self.context.get_or_insert_with(Box::default).span = Some(span);
+        self
+    }
+
+    /// Get the span of the value where the constraint was first violated, if determinable
+    pub fn span(&self) -> Option<Span> {
+        self.context.as_ref().and_then(|c| c.span)
+    }
+
+    /// Attach the value which failed the constraint, e.g. to show it in a diagnostic
+    ///
+    /// Never attach the value of a secret.
+    pub fn with_input<S: Into<String>>(mut self, input: S) -> ConstrainedTypeError {
+        self.context.get_or_insert_with(Box::default).input = Some(input.into());
+        self
+    }
+
+    /// Get the value which failed the constraint, if attached
+    pub fn input(&self) -> Option<&str> {
+        self.context.as_ref().and_then(|c| c.input.as_deref())
+    }
+
+    /// Mark the error as reported against a secret, including every combined error,
+    /// so integrations leave out the reported value
+    pub fn sensitive(mut self) -> ConstrainedTypeError {
+        if let ConstrainedTypeErrorKind::Multiple { errors } = &mut self.kind {
+            *errors = errors.drain(..).map(ConstrainedTypeError::sensitive).collect();
+        }
+
+        self.context.get_or_insert_with(Box::default).sensitive = true;
+        self
+    }
+
+    /// Whether the error is reported against a secret
+    pub fn is_sensitive(&self) -> bool {
+        self.context.as_ref().is_some_and(|c| c.sensitive)
+    }
+
+    /// Attach the human description of the violated pattern, shown instead of the raw regex
+    pub fn with_description<S: Into<String>>(mut self, description: S) -> ConstrainedTypeError {
+        self.context.get_or_insert_with(Box::default).description = Some(description.into());
+        self
+    }
+
+    /// Get the human description of the violated pattern, if attached
+    pub fn description(&self) -> Option<&str> {
+        self.context.as_ref().and_then(|c| c.description.as_deref())
+    }
+
+    /// Attach the messages of the violated pattern rules, shown instead of the raw regex
+    pub fn with_rules(mut self, rules: Vec<String>) -> ConstrainedTypeError {
+        self.context.get_or_insert_with(Box::default).rules = rules;
+        self
+    }
+
+    /// Get the messages of the violated pattern rules, empty if none are attached
+    pub fn rules(&self) -> &[String] {
+        self.context.as_ref().map_or(&[], |c| &c.rules)
+    }
+
+    /// Get the message of the error in another language, joining several errors like `Display`
+    ///
+    /// Errors the localizer has no message for fall back to their English message.
+    pub fn localize<L: Localize + ?Sized>(&self, localizer: &L) -> String {
+        self.errors()
+            .iter()
+            .map(|e| localizer.message(e).unwrap_or_else(|| e.to_string()))
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+
+    /// Get the message of the error like `Display`, with the reported value of every
+    /// [`sensitive`](ConstrainedTypeError::sensitive) error replaced by [`REDACTED`]
+    pub fn to_redacted_string(&self) -> String {
+        match &self.kind {
+            ConstrainedTypeErrorKind::Multiple { errors } => {
+                errors.iter().map(|e| e.to_redacted_string()).collect::<Vec<_>>().join("; ")
+            }
+            _ if self.is_sensitive() => {
+                let mut err = self.clone();
+
+                if let Some(found) = err.kind.found_mut() {
+                    *found = REDACTED.to_string();
+                }
+
+                err.to_string()
+            }
+            _ => self.to_string(),
+        }
+    }
+
+    #[cfg(feature = "diagnostic")]
+    pub(crate) fn input_source(&self) -> Option<&String> {
+        self.context.as_ref().and_then(|c| c.input.as_ref())
+    }
+
+    /// Get the individual errors, flattening combined errors
+    pub fn errors(&self) -> Vec<&ConstrainedTypeError> {
+        match &self.kind {
+            ConstrainedTypeErrorKind::Multiple { errors } => errors.iter().flat_map(|e| e.errors()).collect(),
+            _ => vec![self],
+        }
+    }
 }
 
+impl PartialEq for ConstrainedTypeError {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind
+    }
+}
+
+impl Eq for ConstrainedTypeError {}
+
 impl From<ConstrainedTypeErrorKind> for ConstrainedTypeError {
     fn from(kind: ConstrainedTypeErrorKind) -> ConstrainedTypeError {
-        ConstrainedTypeError { kind }
+        ConstrainedTypeError { kind, context: None }
     }
 }
 
@@ -85,8 +536,42 @@ impl<T> From<ConstrainedTypeError> for Result<T, ConstrainedTypeError> {
     }
 }
 
+/// A translation of error messages, e.g. looked up by [`ConstrainedTypeErrorKind::code`]
+///
+/// ```ignore
+/// let message = err.localize(&|e: &ConstrainedTypeError| match e.kind().code() {
+///     "invalid_option" => Some(format!("{} darf nicht leer sein", e.kind().field_name()?)),
+///     _ => None,
+/// });
+/// ```
+pub trait Localize {
+    /// The message of a single error, `None` to fall back to the English message
+    fn message(&self, err: &ConstrainedTypeError) -> Option<String>;
+}
+
+impl<F> Localize for F
+    where
+        F: Fn(&ConstrainedTypeError) -> Option<String>,
+{
+    fn message(&self, err: &ConstrainedTypeError) -> Option<String> {
+        self(err)
+    }
+}
+
+fn join(errors: &[ConstrainedTypeError]) -> String {
+    errors.iter().map(|e| e.to_string()).collect::<Vec<_>>().join("; ")
+}
+
 impl fmt::Display for ConstrainedTypeError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        fmt::Display::fmt(&self.kind, f)
+        match &self.kind {
+            ConstrainedTypeErrorKind::InvalidPattern { field_name, found, .. } if !self.rules().is_empty() => {
+                write!(f, "{:?} {} for value {:?}", field_name, self.rules().join(" and "), found)
+            }
+            ConstrainedTypeErrorKind::InvalidPattern { field_name, found, .. } if self.description().is_some() => {
+                write!(f, "{:?} must be {} for value {:?}", field_name, self.description().unwrap(), found)
+            }
+            kind => fmt::Display::fmt(kind, f),
+        }
     }
 }
@@ -4,6 +4,8 @@
 
 use num_traits::Float;
 
+use crate::coerce::Adjustment::{ClampedToMax, ClampedToMin, ReplacedNaN};
+use crate::coerce::Coerced;
 use crate::error::ConstrainedTypeErrorKind::{InvalidMaxVal, InvalidMinVal};
 use crate::error::{ConstrainedTypeError, ConstrainedTypeResult};
 use core::fmt;
@@ -39,14 +41,57 @@ pub fn new_float<T, F, V>(
     Ok(ctor(val))
 }
 
+/// A builder function clamping a floating point number into a min/max range
+///
+/// NaN is out of every range, so it is replaced by the min value.
+pub fn coerce_float<T, F, V>(
+    field_name: &str,
+    ctor: F,
+    min_val: V,
+    max_val: V,
+    val: V,
+) -> Coerced<T>
+    where
+        F: Fn(V) -> T,
+        V: Float + fmt::Display + fmt::Debug,
+{
+    if val.is_nan() {
+        return Coerced::new(ctor(min_val), Some(ReplacedNaN {
+            field_name: field_name.to_string(),
+            expected: min_val.to_string(),
+        }));
+    }
+
+    if val < min_val {
+        return Coerced::new(ctor(min_val), Some(ClampedToMin {
+            field_name: field_name.to_string(),
+            expected: min_val.to_string(),
+            found: val.to_string(),
+        }));
+    }
+
+    if val > max_val {
+        return Coerced::new(ctor(max_val), Some(ClampedToMax {
+            field_name: field_name.to_string(),
+            expected: max_val.to_string(),
+            found: val.to_string(),
+        }));
+    }
+
+    Coerced::new(ctor(val), None)
+}
+
 #[cfg(test)]
+#[allow(clippy::redundant_closure)]
 mod test {
+    use crate::coerce::Adjustment::{ClampedToMax, ClampedToMin, ReplacedNaN};
     use crate::error::ConstrainedTypeError;
     use crate::error::ConstrainedTypeErrorKind::{InvalidMaxVal, InvalidMinVal};
 
     mod kilogram_quantity {
+        use crate::coerce::Coerced;
         use crate::error::ConstrainedTypeResult;
-        use crate::float::new_float;
+        use crate::float::{coerce_float, new_float};
 
         #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
         pub struct KilogramQuantity(f32);
@@ -64,6 +109,10 @@ mod test {
         pub fn new(field_name: &str, value: f32) -> ConstrainedTypeResult<KilogramQuantity> {
             new_float(field_name, |v| KilogramQuantity::new(v), 0.05, 100.0, value)
         }
+
+        pub fn coerce(field_name: &str, value: f32) -> Coerced<KilogramQuantity> {
+            coerce_float(field_name, |v| KilogramQuantity::new(v), 0.05, 100.0, value)
+        }
     }
 
     #[test]
@@ -91,4 +140,53 @@ mod test {
     fn it_can_construct_an_kilogram_quantity() {
         assert_eq!(kilogram_quantity::new("qty", 1.0).unwrap().value(), 1.0);
     }
+
+    #[test]
+    fn it_clamps_out_of_bounds_value() {
+        let qty = kilogram_quantity::coerce("qty", 0.04);
+
+        assert_eq!(qty.value().value(), 0.05);
+        assert_eq!(
+            qty.adjustment(),
+            Some(&ClampedToMin {
+                field_name: "qty".to_string(),
+                expected: (0.05).to_string(),
+                found: (0.04).to_string(),
+            })
+        );
+
+        let qty = kilogram_quantity::coerce("qty", 100.1);
+
+        assert_eq!(qty.value().value(), 100.0);
+        assert_eq!(
+            qty.adjustment(),
+            Some(&ClampedToMax {
+                field_name: "qty".to_string(),
+                expected: (100).to_string(),
+                found: (100.1).to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn it_replaces_nan_with_the_min_value() {
+        let qty = kilogram_quantity::coerce("qty", f32::NAN);
+
+        assert_eq!(qty.value().value(), 0.05);
+        assert_eq!(
+            qty.adjustment(),
+            Some(&ReplacedNaN {
+                field_name: "qty".to_string(),
+                expected: (0.05).to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn it_keeps_an_in_bounds_kilogram_quantity() {
+        let qty = kilogram_quantity::coerce("qty", 1.0);
+
+        assert!(!qty.is_adjusted());
+        assert_eq!(qty.into_value().value(), 1.0);
+    }
 }
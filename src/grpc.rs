@@ -0,0 +1,80 @@
+//! gRPC status
+
+#![deny(missing_docs)]
+
+use tonic::{Code, Status};
+use tonic_types::{ErrorDetails, FieldViolation, StatusExt};
+
+use crate::error::ConstrainedTypeError;
+
+/// Get a `google.rpc.BadRequest` field violation for every error
+pub fn field_violations(err: &ConstrainedTypeError) -> Vec<FieldViolation> {
+    err.errors()
+        .into_iter()
+        .map(|e| FieldViolation::new(e.kind().field_name().unwrap_or_default(), e.to_redacted_string()))
+        .collect()
+}
+
+/// Converts the error into an `INVALID_ARGUMENT` status carrying a `google.rpc.BadRequest`
+/// with a field violation for every error
+impl From<ConstrainedTypeError> for Status {
+    fn from(err: ConstrainedTypeError) -> Self {
+        Status::with_error_details(
+            Code::InvalidArgument,
+            err.to_redacted_string(),
+            ErrorDetails::with_bad_request(field_violations(&err)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tonic::{Code, Status};
+    use tonic_types::StatusExt;
+
+    use crate::error::ConstrainedTypeError;
+    use crate::error::ConstrainedTypeErrorKind::{InvalidMaxLen, InvalidMinVal, InvalidPattern};
+    use crate::grpc::field_violations;
+
+    #[test]
+    fn it_attaches_a_field_violation_for_every_error() {
+        let err = ConstrainedTypeError::multiple(vec![
+            ConstrainedTypeError::from(InvalidMaxLen {
+                field_name: "lines[3]".to_string(),
+                expected: (5).to_string(),
+                found: (6).to_string(),
+            }),
+            ConstrainedTypeError::from(InvalidMinVal {
+                field_name: "age".to_string(),
+                expected: (18).to_string(),
+                found: (12).to_string(),
+            }),
+        ]);
+
+        let status = Status::from(err);
+
+        assert_eq!(status.code(), Code::InvalidArgument);
+
+        let violations = status.get_details_bad_request().unwrap().field_violations;
+
+        assert_eq!(
+            violations.iter().map(|v| (v.field.as_str(), v.description.as_str())).collect::<Vec<_>>(),
+            vec![
+                ("lines[3]", "\"lines[3]\" must not be greater than \"5\" characters, \"6\""),
+                ("age", "\"age\" must not be less than \"18\", \"12\""),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_redacts_the_value_of_sensitive_errors() {
+        let err = ConstrainedTypeError::from(InvalidPattern {
+            field_name: "api_key".to_string(),
+            expected: "^key_[a-z0-9]+$".to_string(),
+            found: "key_SECRET".to_string(),
+        }).sensitive();
+
+        assert!(!field_violations(&err)[0].description.contains("key_SECRET"));
+        assert!(!Status::from(err).message().contains("key_SECRET"));
+    }
+}
@@ -0,0 +1,250 @@
+//! Password hash
+
+#![deny(missing_docs)]
+
+use core::fmt;
+use std::convert::TryFrom;
+
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{PasswordHasher, PasswordVerifier, SaltString};
+use argon2::{Algorithm, Argon2, Params, Version, ARGON2ID_IDENT};
+use zeroize::Zeroizing;
+
+use crate::error::ConstrainedTypeErrorKind::{Consumed, InvalidHash};
+use crate::error::{ConstrainedTypeError, ConstrainedTypeResult};
+use crate::secret::{constant_time_eq, Secret};
+
+/// The argon2id cost parameters used to hash a password
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HashParams {
+    /// Memory size in KiB
+    pub m_cost: u32,
+    /// Number of iterations
+    pub t_cost: u32,
+    /// Degree of parallelism
+    pub p_cost: u32,
+}
+
+impl Default for HashParams {
+    fn default() -> Self {
+        Self {
+            m_cost: Params::DEFAULT_M_COST,
+            t_cost: Params::DEFAULT_T_COST,
+            p_cost: Params::DEFAULT_P_COST,
+        }
+    }
+}
+
+impl HashParams {
+    /// The highest cost parameters accepted from a stored hash by default, four times the defaults
+    pub fn default_max() -> Self {
+        let params = Self::default();
+
+        Self {
+            m_cost: params.m_cost * 4,
+            t_cost: params.t_cost * 4,
+            p_cost: params.p_cost * 4,
+        }
+    }
+
+    fn exceeds(&self, max_params: &HashParams) -> bool {
+        self.m_cost > max_params.m_cost || self.t_cost > max_params.t_cost || self.p_cost > max_params.p_cost
+    }
+}
+
+impl fmt::Display for HashParams {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "m={},t={},p={}", self.m_cost, self.t_cost, self.p_cost)
+    }
+}
+
+/// An argon2id password hash in PHC string format
+///
+/// It can only be created by hashing a password or by parsing a valid stored hash,
+/// so it never holds the plaintext. Hashes are compared in constant time.
+#[derive(Debug, Clone)]
+pub struct PasswordHash(String);
+
+impl PasswordHash {
+    /// Get the PHC string, e.g. to store it
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+
+    /// Whether the candidate password matches the hash, compared in constant time
+    pub fn verify(&self, candidate: &str) -> bool {
+        match argon2::PasswordHash::new(&self.0) {
+            Ok(hash) => Argon2::default().verify_password(candidate.as_bytes(), &hash).is_ok(),
+            Err(_) => false,
+        }
+    }
+}
+
+impl PartialEq for PasswordHash {
+    fn eq(&self, other: &Self) -> bool {
+        constant_time_eq(self.0.as_bytes(), other.0.as_bytes())
+    }
+}
+
+impl Eq for PasswordHash {}
+
+impl fmt::Display for PasswordHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Hash a validated secret password with argon2id and a random salt
+///
+/// The secret is consumed and its plaintext zeroized once hashed. Errors if it was consumed before.
+pub fn hash_password(
+    field_name: &str,
+    params: &HashParams,
+    mut secret: Secret<String>,
+) -> ConstrainedTypeResult<PasswordHash> {
+    let plaintext = match secret.consume() {
+        Some(v) => Zeroizing::new(v),
+        None => {
+            return ConstrainedTypeError::from(Consumed {
+                field_name: field_name.to_string(),
+            }).into();
+        }
+    };
+
+    let invalid_hash = |e: argon2::password_hash::Error| ConstrainedTypeError::from(InvalidHash {
+        field_name: field_name.to_string(),
+        reason: e.to_string(),
+    });
+
+    let argon2 = Params::new(params.m_cost, params.t_cost, params.p_cost, None)
+        .map(|p| Argon2::new(Algorithm::Argon2id, Version::V0x13, p))
+        .map_err(|e| invalid_hash(e.into()))?;
+
+    let salt = SaltString::generate(&mut OsRng);
+    let hash = argon2.hash_password(plaintext.as_bytes(), &salt).map_err(invalid_hash)?;
+
+    Ok(PasswordHash(hash.to_string()))
+}
+
+/// A builder function constraining a String to be a valid argon2id hash in PHC string format
+///
+/// Verifying runs with the cost parameters of the hash, so hashes with parameters above
+/// `max_params` are rejected, e.g. pass [`HashParams::default_max`].
+pub fn new_password_hash(field_name: &str, max_params: &HashParams, val: &str) -> ConstrainedTypeResult<PasswordHash> {
+    let invalid_hash = |reason: String| ConstrainedTypeError::from(InvalidHash {
+        field_name: field_name.to_string(),
+        reason,
+    });
+
+    let hash = argon2::PasswordHash::new(val).map_err(|e| invalid_hash(e.to_string()))?;
+
+    if hash.algorithm != ARGON2ID_IDENT {
+        return invalid_hash(format!("unsupported algorithm {}", hash.algorithm)).into();
+    }
+
+    if hash.salt.is_none() || hash.hash.is_none() {
+        return invalid_hash("missing salt or hash".to_string()).into();
+    }
+
+    let params = Params::try_from(&hash).map_err(|e| invalid_hash(e.to_string()))?;
+    let params = HashParams {
+        m_cost: params.m_cost(),
+        t_cost: params.t_cost(),
+        p_cost: params.p_cost(),
+    };
+
+    if params.exceeds(max_params) {
+        return invalid_hash(format!("cost parameters {} exceed {}", params, max_params)).into();
+    }
+
+    Ok(PasswordHash(val.to_string()))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::ConstrainedTypeError;
+    use crate::error::ConstrainedTypeErrorKind::{Consumed, InvalidHash};
+    use crate::hash::{hash_password, new_password_hash, HashParams};
+    use crate::secret::Secret;
+
+    const PARAMS: HashParams = HashParams {
+        m_cost: 1024,
+        t_cost: 1,
+        p_cost: 1,
+    };
+
+    #[test]
+    fn it_hashes_and_verifies_a_password() {
+        let hash = hash_password("password", &PARAMS, Secret::new("S3cret!pass".to_string())).unwrap();
+
+        assert!(hash.value().starts_with("$argon2id$v=19$m=1024,t=1,p=1$"));
+        assert!(hash.verify("S3cret!pass"));
+        assert!(!hash.verify("S3cret!pas"));
+
+        assert_eq!(new_password_hash("password_hash", &PARAMS, hash.value()), Ok(hash.clone()));
+        assert_ne!(hash_password("password", &PARAMS, Secret::new("S3cret!pass".to_string())), Ok(hash));
+    }
+
+    #[test]
+    fn it_errors_on_invalid_stored_hash() {
+        assert_eq!(
+            new_password_hash("password_hash", &PARAMS, "S3cret!pass"),
+            ConstrainedTypeError::from(InvalidHash {
+                field_name: "password_hash".to_string(),
+                reason: "password hash string missing field".to_string(),
+            }).into()
+        );
+
+        assert_eq!(
+            new_password_hash(
+                "password_hash",
+                &PARAMS,
+                "$argon2i$v=19$m=1024,t=1,p=1$c29tZXNhbHQ$iWh06vD8Fy27wf9npn6FXWiCX4K6pW6Ue1Bnzz07Z8A"
+            ),
+            ConstrainedTypeError::from(InvalidHash {
+                field_name: "password_hash".to_string(),
+                reason: "unsupported algorithm argon2i".to_string(),
+            }).into()
+        );
+    }
+
+    #[test]
+    fn it_errors_on_stored_hash_above_max_params() {
+        let hash = hash_password("password", &PARAMS, Secret::new("S3cret!pass".to_string())).unwrap();
+        let max_params = HashParams { m_cost: 512, ..PARAMS };
+
+        assert_eq!(
+            new_password_hash("password_hash", &max_params, hash.value()),
+            ConstrainedTypeError::from(InvalidHash {
+                field_name: "password_hash".to_string(),
+                reason: "cost parameters m=1024,t=1,p=1 exceed m=512,t=1,p=1".to_string(),
+            }).into()
+        );
+
+        assert_eq!(
+            new_password_hash(
+                "password_hash",
+                &HashParams::default_max(),
+                "$argon2id$v=19$m=4194304,t=1,p=1$c29tZXNhbHQ$iWh06vD8Fy27wf9npn6FXWiCX4K6pW6Ue1Bnzz07Z8A"
+            ),
+            ConstrainedTypeError::from(InvalidHash {
+                field_name: "password_hash".to_string(),
+                reason: "cost parameters m=4194304,t=1,p=1 exceed m=77824,t=8,p=4".to_string(),
+            }).into()
+        );
+    }
+
+    #[test]
+    fn it_errors_on_consumed_secret() {
+        let mut secret = Secret::new("S3cret!pass".to_string());
+
+        secret.consume();
+
+        assert_eq!(
+            hash_password("password", &PARAMS, secret),
+            ConstrainedTypeError::from(Consumed {
+                field_name: "password".to_string(),
+            }).into()
+        );
+    }
+}
@@ -4,6 +4,8 @@
 
 use num_traits::PrimInt;
 
+use crate::coerce::Adjustment::{ClampedToMax, ClampedToMin};
+use crate::coerce::Coerced;
 use crate::error::ConstrainedTypeErrorKind::{InvalidMaxVal, InvalidMinVal};
 use crate::error::{ConstrainedTypeError, ConstrainedTypeResult};
 use core::fmt;
@@ -39,14 +41,48 @@ pub fn new_int<T, F, V>(
     Ok(ctor(val))
 }
 
+/// A builder function clamping an integer number into the range between a minimum and maximum value
+pub fn coerce_int<T, F, V>(
+    field_name: &str,
+    ctor: F,
+    min_val: V,
+    max_val: V,
+    val: V,
+) -> Coerced<T>
+    where
+        F: Fn(V) -> T,
+        V: PrimInt + fmt::Display + fmt::Debug,
+{
+    if val < min_val {
+        return Coerced::new(ctor(min_val), Some(ClampedToMin {
+            field_name: field_name.to_string(),
+            expected: min_val.to_string(),
+            found: val.to_string(),
+        }));
+    }
+
+    if val > max_val {
+        return Coerced::new(ctor(max_val), Some(ClampedToMax {
+            field_name: field_name.to_string(),
+            expected: max_val.to_string(),
+            found: val.to_string(),
+        }));
+    }
+
+    Coerced::new(ctor(val), None)
+}
+
 #[cfg(test)]
+#[allow(clippy::redundant_closure)]
 mod test {
+    use crate::coerce::Adjustment::{ClampedToMax, ClampedToMin};
     use crate::error::ConstrainedTypeError;
     use crate::error::ConstrainedTypeErrorKind::{InvalidMaxVal, InvalidMinVal};
 
     mod unit_quantity {
+        use crate::coerce::Coerced;
         use crate::error::ConstrainedTypeResult;
-        use crate::int::new_int;
+        use crate::int::{coerce_int, new_int};
 
         const MIN_VAL: u16 = 1;
         const MAX_VAL: u16 = 1000;
@@ -73,6 +109,16 @@ mod test {
                 value,
             )
         }
+
+        pub fn coerce(field_name: &str, value: u16) -> Coerced<UnitQuantity> {
+            coerce_int(
+                field_name,
+                |v| UnitQuantity::new(v),
+                MIN_VAL,
+                MAX_VAL,
+                value,
+            )
+        }
     }
 
     #[test]
@@ -100,4 +146,39 @@ mod test {
     fn it_can_construct_a_unit_quantity() {
         assert_eq!(unit_quantity::new("qty", 1).unwrap().value(), 1);
     }
+
+    #[test]
+    fn it_clamps_out_of_bounds_value() {
+        let qty = unit_quantity::coerce("qty", 0);
+
+        assert_eq!(qty.value().value(), 1);
+        assert_eq!(
+            qty.adjustment(),
+            Some(&ClampedToMin {
+                field_name: "qty".to_string(),
+                expected: 1.to_string(),
+                found: 0.to_string(),
+            })
+        );
+
+        let qty = unit_quantity::coerce("qty", 1001);
+
+        assert_eq!(qty.value().value(), 1000);
+        assert_eq!(
+            qty.adjustment(),
+            Some(&ClampedToMax {
+                field_name: "qty".to_string(),
+                expected: 1000.to_string(),
+                found: 1001.to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn it_keeps_an_in_bounds_unit_quantity() {
+        let qty = unit_quantity::coerce("qty", 1);
+
+        assert!(!qty.is_adjusted());
+        assert_eq!(qty.into_value().value(), 1);
+    }
 }
@@ -1,6 +1,41 @@
+#[cfg(feature = "breach")]
+pub mod breach;
+pub mod blocklist;
+pub mod charset;
+#[cfg(feature = "cli")]
+pub mod cli;
+pub mod coerce;
+pub mod collection;
+#[cfg(feature = "confusable")]
+pub mod confusable;
+pub mod constraint;
+#[cfg(feature = "database")]
+pub mod database;
+#[cfg(feature = "datetime")]
+pub mod datetime;
+#[cfg(feature = "diagnostic")]
+pub mod diagnostic;
 pub mod error;
 pub mod float;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+#[cfg(feature = "hash")]
+pub mod hash;
 pub mod int;
+pub mod password;
+#[cfg(feature = "problem")]
+pub mod problem;
+pub mod regex_compat;
+#[cfg(feature = "schema")]
+pub mod schema;
+pub mod secret;
+pub mod sql;
+#[cfg(feature = "strength")]
+pub mod strength;
 pub mod string;
 pub mod string_like;
 pub mod string_option;
+pub mod typescript;
+pub mod validate;
+#[cfg(feature = "web")]
+pub mod web;
@@ -0,0 +1,286 @@
+//! Constrained password
+
+#![deny(missing_docs)]
+
+use core::fmt;
+
+use unicode_general_category::{get_general_category, GeneralCategory};
+use zeroize::Zeroizing;
+
+use crate::error::ConstrainedTypeErrorKind::{
+    InvalidCharClass, InvalidMaxLen, InvalidMinLen, InvalidRepetition, InvalidSubstring,
+};
+use crate::error::{ConstrainedTypeError, ConstrainedTypeResult, Span};
+use crate::secret::Secret;
+
+/// A class of characters a password can be required to contain
+///
+/// Every class is Unicode-aware, so e.g. `é` is a lower-case letter and `٣` a digit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CharClass {
+    /// A decimal digit of any script, Unicode general category `Nd`
+    Digit,
+    /// A lower-case letter
+    Lowercase,
+    /// An upper-case letter
+    Uppercase,
+    /// Any character that is neither alphanumeric nor whitespace
+    Special,
+}
+
+impl CharClass {
+    fn contains(&self, c: char) -> bool {
+        match self {
+            CharClass::Digit => get_general_category(c) == GeneralCategory::DecimalNumber,
+            CharClass::Lowercase => c.is_lowercase(),
+            CharClass::Uppercase => c.is_uppercase(),
+            CharClass::Special => !c.is_alphanumeric() && !c.is_whitespace(),
+        }
+    }
+}
+
+impl fmt::Display for CharClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CharClass::Digit => write!(f, "digit"),
+            CharClass::Lowercase => write!(f, "lower-case letter"),
+            CharClass::Uppercase => write!(f, "upper-case letter"),
+            CharClass::Special => write!(f, "special character"),
+        }
+    }
+}
+
+/// The rules a password has to satisfy
+///
+/// Every rule is checked, so a rejected password reports each failed rule as its own error.
+/// Errors never contain the password itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PasswordPolicy {
+    /// Minimum number of characters
+    pub min_len: usize,
+    /// Maximum number of characters
+    pub max_len: usize,
+    /// Classes of characters that must each appear at least once
+    pub required: Vec<CharClass>,
+    /// Maximum number of times the same character may appear in a row
+    pub max_repeated: Option<usize>,
+    /// Terms the password must not contain regardless of case, e.g. the username
+    pub disallowed: Vec<String>,
+}
+
+impl Default for PasswordPolicy {
+    fn default() -> Self {
+        Self {
+            min_len: 8,
+            max_len: 64,
+            required: vec![],
+            max_repeated: None,
+            disallowed: vec![],
+        }
+    }
+}
+
+impl PasswordPolicy {
+    /// Check a password against every rule of the policy
+    pub fn check(&self, field_name: &str, val: &str) -> ConstrainedTypeResult<()> {
+        let mut errors = vec![];
+        let len = val.chars().count();
+
+        if len < self.min_len {
+            errors.push(ConstrainedTypeError::from(InvalidMinLen {
+                field_name: field_name.to_string(),
+                expected: self.min_len.to_string(),
+                found: len.to_string(),
+            }));
+        }
+
+        if len > self.max_len {
+            errors.push(ConstrainedTypeError::from(InvalidMaxLen {
+                field_name: field_name.to_string(),
+                expected: self.max_len.to_string(),
+                found: len.to_string(),
+            }).with_span(Span::from_char(val, self.max_len)));
+        }
+
+        for class in &self.required {
+            if !val.chars().any(|c| class.contains(c)) {
+                errors.push(ConstrainedTypeError::from(InvalidCharClass {
+                    field_name: field_name.to_string(),
+                    expected: class.to_string(),
+                }));
+            }
+        }
+
+        if let Some(max_repeated) = self.max_repeated {
+            let (repeated, span) = longest_repetition(val);
+
+            if repeated > max_repeated {
+                errors.push(ConstrainedTypeError::from(InvalidRepetition {
+                    field_name: field_name.to_string(),
+                    expected: max_repeated.to_string(),
+                    found: repeated.to_string(),
+                }).with_span(span));
+            }
+        }
+
+        let lowercase = Zeroizing::new(val.to_lowercase());
+
+        for term in &self.disallowed {
+            if !term.is_empty() && lowercase.contains(&term.to_lowercase()) {
+                errors.push(ConstrainedTypeError::from(InvalidSubstring {
+                    field_name: field_name.to_string(),
+                    found: term.to_string(),
+                }));
+            }
+        }
+
+        if !errors.is_empty() {
+            return ConstrainedTypeError::multiple(errors).into();
+        }
+
+        Ok(())
+    }
+}
+
+fn longest_repetition(val: &str) -> (usize, Span) {
+    let mut longest = (0, Span::new(val, 0, 0));
+    let mut current = 0;
+    let mut start = 0;
+    let mut last = None;
+
+    for (idx, c) in val.char_indices() {
+        if last == Some(c) {
+            current += 1;
+        } else {
+            current = 1;
+            start = idx;
+        }
+
+        if current > longest.0 {
+            longest = (current, Span::new(val, start, idx + c.len_utf8()));
+        }
+
+        last = Some(c);
+    }
+
+    longest
+}
+
+/// A builder function constraining a password to satisfy a policy
+///
+/// The error is marked [`sensitive`](ConstrainedTypeError::sensitive).
+pub fn new_password<T, F>(
+    field_name: &str,
+    ctor: F,
+    policy: &PasswordPolicy,
+    val: &str,
+) -> ConstrainedTypeResult<T>
+    where
+        F: Fn(Secret<String>) -> T,
+{
+    policy.check(field_name, val).map_err(ConstrainedTypeError::sensitive)?;
+
+    Ok(ctor(Secret::new(val.to_string())))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::{ConstrainedTypeError, Span};
+    use crate::error::ConstrainedTypeErrorKind::{
+        InvalidCharClass, InvalidMinLen, InvalidRepetition, InvalidSubstring,
+    };
+
+    mod password {
+        use crate::error::ConstrainedTypeResult;
+        use crate::password::{new_password, CharClass, PasswordPolicy};
+        use crate::secret::Secret;
+
+        #[derive(Debug)]
+        pub struct Password(Secret<String>);
+
+        impl Password {
+            pub(crate) fn new(secret: Secret<String>) -> Password {
+                Self(secret)
+            }
+
+            pub fn value(&mut self) -> Option<String> {
+                self.0.consume()
+            }
+        }
+
+        pub fn new(field_name: &str, raw: &str, username: &str) -> ConstrainedTypeResult<Password> {
+            let policy = PasswordPolicy {
+                min_len: 8,
+                max_len: 32,
+                required: vec![CharClass::Digit, CharClass::Uppercase, CharClass::Special],
+                max_repeated: Some(2),
+                disallowed: vec![username.to_string()],
+            };
+
+            new_password(field_name, Password::new, &policy, raw)
+        }
+    }
+
+    #[test]
+    fn it_reports_every_failed_rule() {
+        let err = password::new("password", "johnnn", "John").unwrap_err();
+
+        assert_eq!(
+            err.errors(),
+            vec![
+                &ConstrainedTypeError::from(InvalidMinLen {
+                    field_name: "password".to_string(),
+                    expected: (8).to_string(),
+                    found: (6).to_string(),
+                }),
+                &ConstrainedTypeError::from(InvalidCharClass {
+                    field_name: "password".to_string(),
+                    expected: "digit".to_string(),
+                }),
+                &ConstrainedTypeError::from(InvalidCharClass {
+                    field_name: "password".to_string(),
+                    expected: "upper-case letter".to_string(),
+                }),
+                &ConstrainedTypeError::from(InvalidCharClass {
+                    field_name: "password".to_string(),
+                    expected: "special character".to_string(),
+                }),
+                &ConstrainedTypeError::from(InvalidRepetition {
+                    field_name: "password".to_string(),
+                    expected: (2).to_string(),
+                    found: (3).to_string(),
+                }),
+                &ConstrainedTypeError::from(InvalidSubstring {
+                    field_name: "password".to_string(),
+                    found: "John".to_string(),
+                }),
+            ]
+        );
+
+        assert_eq!(err.errors()[4].span(), Some(Span { start: 3, end: 6, char_start: 3 }));
+    }
+
+    #[test]
+    fn it_keeps_a_single_failed_rule_as_it_is() {
+        assert_eq!(
+            password::new("password", "Secret!pass", "john").unwrap_err(),
+            ConstrainedTypeError::from(InvalidCharClass {
+                field_name: "password".to_string(),
+                expected: "digit".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn it_can_construct_a_password() {
+        assert_eq!(
+            password::new("password", "S3cret!pass", "john").unwrap().value(),
+            Some("S3cret!pass".to_string())
+        );
+
+        assert_eq!(
+            password::new("password", "S٣cret!pass", "john").unwrap().value(),
+            Some("S٣cret!pass".to_string())
+        );
+    }
+}
@@ -0,0 +1,212 @@
+//! Problem details for HTTP APIs
+
+#![deny(missing_docs)]
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::ConstrainedTypeErrorKind::*;
+use crate::error::ConstrainedTypeError;
+
+/// The media type of a serialized [`Problem`]
+pub const CONTENT_TYPE: &str = "application/problem+json";
+
+/// Validation errors as RFC 9457 problem details, listing each error in an `errors` extension member
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Problem {
+    /// URI identifying the problem type
+    #[serde(rename = "type")]
+    pub type_uri: String,
+    /// Short summary of the problem type
+    pub title: String,
+    /// HTTP status code
+    pub status: u16,
+    /// Explanation specific to this occurrence of the problem
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// URI identifying this occurrence of the problem
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    /// Errors of each field
+    pub errors: Vec<ProblemError>,
+}
+
+/// A single error of a [`Problem`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProblemError {
+    /// Human-readable message
+    pub detail: String,
+    /// JSON Pointer to the field, e.g. `#/lines/3` for the field name `lines[3]`
+    pub pointer: String,
+    /// Machine-readable code of the error kind, e.g. `invalid_max_len`
+    pub code: String,
+    /// Specified constraint
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected: Option<String>,
+    /// Actual value, omitted when the error is sensitive
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub found: Option<String>,
+}
+
+impl Problem {
+    /// Create a 422 Unprocessable Content problem listing every error of the given errors
+    pub fn from_errors<'a, I>(errors: I) -> Self
+        where
+            I: IntoIterator<Item=&'a ConstrainedTypeError>,
+    {
+        let errors = errors
+            .into_iter()
+            .flat_map(|e| e.errors())
+            .map(ProblemError::from)
+            .collect();
+
+        Self {
+            type_uri: "about:blank".to_string(),
+            title: "Unprocessable Content".to_string(),
+            status: 422,
+            detail: None,
+            instance: None,
+            errors,
+        }
+    }
+
+    /// Serialize the problem as JSON
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("problem details always serialize")
+    }
+}
+
+impl From<&ConstrainedTypeError> for Problem {
+    fn from(err: &ConstrainedTypeError) -> Self {
+        Self::from_errors(std::iter::once(err))
+    }
+}
+
+impl From<&ConstrainedTypeError> for ProblemError {
+    fn from(err: &ConstrainedTypeError) -> Self {
+        let (expected, found) = constraint(err);
+
+        Self {
+            detail: err.to_redacted_string(),
+            pointer: pointer(err.kind().field_name().unwrap_or_default()),
+            code: err.kind().code().to_string(),
+            expected: expected.map(str::to_string),
+            found: found.filter(|_| !err.is_sensitive()).map(str::to_string),
+        }
+    }
+}
+
+/// Convert a field name like `metadata[env]` into a JSON Pointer fragment like `#/metadata/env`
+fn pointer(field_name: &str) -> String {
+    let mut pointer = "#".to_string();
+
+    for token in field_name.split(['[', ']']).filter(|t| !t.is_empty()) {
+        pointer.push('/');
+        pointer.push_str(&token.replace('~', "~0").replace('/', "~1"));
+    }
+
+    pointer
+}
+
+fn constraint(err: &ConstrainedTypeError) -> (Option<&str>, Option<&str>) {
+    match err.kind() {
+        InvalidPattern { expected, found, .. } => (Some(err.description().unwrap_or(expected)), Some(found)),
+        InvalidMaxVal { expected, found, .. }
+        | InvalidMinVal { expected, found, .. }
+        | InvalidMaxLen { expected, found, .. }
+        | InvalidMinItems { expected, found, .. }
+        | InvalidMaxItems { expected, found, .. }
+        | InvalidWeekday { expected, found, .. }
+        | InvalidTime { expected, found, .. }
+        | InvalidMinLen { expected, found, .. }
+        | InvalidRepetition { expected, found, .. }
+        | WeakPassword { expected, found, .. }
+        | Reserved { expected, found, .. }
+        | Confusable { expected, found, .. }
+        | InvalidChar { expected, found, .. } => (Some(expected), Some(found)),
+        InvalidUnique { found, .. } | InvalidSubstring { found, .. } | InvalidScript { found, .. } => {
+            (None, Some(found))
+        }
+        InvalidCharClass { expected, .. } => (Some(expected), None),
+        InvalidOption { .. }
+        | Breached { .. }
+        | Unverifiable { .. }
+        | InvalidHash { .. }
+        | Consumed { .. }
+        | Multiple { .. } => (None, None),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::{json, Value};
+
+    use crate::error::ConstrainedTypeError;
+    use crate::error::ConstrainedTypeErrorKind::{InvalidMaxLen, InvalidOption, InvalidPattern};
+    use crate::problem::Problem;
+    use crate::secret::new_secret_string;
+
+    #[test]
+    fn it_renders_problem_details_with_every_error() {
+        let err = ConstrainedTypeError::multiple(vec![
+            ConstrainedTypeError::from(InvalidMaxLen {
+                field_name: "lines[3]".to_string(),
+                expected: (5).to_string(),
+                found: (6).to_string(),
+            }),
+            ConstrainedTypeError::from(InvalidOption {
+                field_name: "metadata[a/b]".to_string(),
+            }),
+        ]);
+
+        assert_eq!(
+            serde_json::from_str::<Value>(&Problem::from(&err).to_json()).unwrap(),
+            json!({
+                "type": "about:blank",
+                "title": "Unprocessable Content",
+                "status": 422,
+                "errors": [
+                    {
+                        "detail": "\"lines[3]\" must not be greater than \"5\" characters, \"6\"",
+                        "pointer": "#/lines/3",
+                        "code": "invalid_max_len",
+                        "expected": "5",
+                        "found": "6",
+                    },
+                    {
+                        "detail": "\"metadata[a/b]\" must not be empty",
+                        "pointer": "#/metadata/a~1b",
+                        "code": "invalid_option",
+                    },
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn it_omits_sensitive_values() {
+        let err = ConstrainedTypeError::from(InvalidPattern {
+            field_name: "api_key".to_string(),
+            expected: "^key_[a-z0-9]+$".to_string(),
+            found: "key_SECRET".to_string(),
+        }).with_description("an API key").sensitive();
+        let other = ConstrainedTypeError::from(InvalidMaxLen {
+            field_name: "name".to_string(),
+            expected: (5).to_string(),
+            found: (6).to_string(),
+        });
+
+        let problem = Problem::from_errors(vec![&err, &other]);
+
+        assert_eq!(problem.errors.len(), 2);
+        assert_eq!(problem.errors[0].pointer, "#/api_key");
+        assert_eq!(problem.errors[0].expected.as_deref(), Some("an API key"));
+        assert_eq!(problem.errors[0].found, None);
+        assert_eq!(problem.errors[1].found.as_deref(), Some("6"));
+        assert!(!problem.to_json().contains("key_SECRET"));
+
+        let token = new_secret_string("token", |v| v, 8, "key_abc123").unwrap_err();
+        let both = ConstrainedTypeError::multiple(vec![other, token]).sensitive();
+
+        assert!(!Problem::from(&both).to_json().contains("found"));
+    }
+}
@@ -0,0 +1,523 @@
+//! Regex compatibility with other engines
+
+#![deny(missing_docs)]
+
+use core::fmt;
+
+/// A fancy-regex feature without an equivalent in the engine a pattern is exported to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RegexFeature {
+    /// `(?<=...)` and `(?<!...)`, missing in older engines like Safari before 16.4
+    Lookbehind,
+    /// `(?>...)`
+    AtomicGroup,
+    /// `*+`, `++`, `?+` and `{n}+`
+    PossessiveQuantifier,
+    /// `(?i)` and other inline flags
+    InlineFlags,
+    /// `[[:alpha:]]` and other POSIX classes
+    PosixClass,
+    /// `\b`, `\B`, `\<` and `\>`, which are ASCII-only in JavaScript and locale-dependent in PostgreSQL
+    WordBoundary,
+    /// Nested classes like `[a[bc]]`, the `&&`, `--` and `~~` set operations and `\W` inside a class
+    ClassSetOperation,
+    /// Unicode classes like `\p{L}`, `\d`, `\w` and `\s` the engine cannot express
+    UnicodeClass,
+    /// An escape sequence the engine does not know
+    Escape,
+    /// A repetition count above 255, the limit of PostgreSQL
+    RepetitionLimit,
+}
+
+impl fmt::Display for RegexFeature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            RegexFeature::Lookbehind => "lookbehind",
+            RegexFeature::AtomicGroup => "atomic group",
+            RegexFeature::PossessiveQuantifier => "possessive quantifier",
+            RegexFeature::InlineFlags => "inline flags",
+            RegexFeature::PosixClass => "POSIX character class",
+            RegexFeature::WordBoundary => "word boundary",
+            RegexFeature::ClassSetOperation => "nested class or class set operation",
+            RegexFeature::UnicodeClass => "Unicode class",
+            RegexFeature::Escape => "escape sequence",
+            RegexFeature::RepetitionLimit => "repetition count above 255",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+/// The engine a pattern is translated for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Engine {
+    /// ECMAScript with the `u` flag, written as the body of a regex literal
+    JavaScript,
+    /// PostgreSQL advanced regular expressions
+    Postgres,
+}
+
+/// The Unicode `\w` of fancy-regex as the content of a JavaScript class
+const WORD: &str = r"\p{Alphabetic}\p{M}\p{Nd}\p{Pc}\p{Join_Control}";
+
+const GENERAL_CATEGORIES: &[&str] = &[
+    "C", "Other", "Cc", "Control", "Cf", "Format", "Cn", "Unassigned", "Co", "Private_Use", "Cs", "Surrogate",
+    "L", "Letter", "LC", "Cased_Letter", "Ll", "Lowercase_Letter", "Lm", "Modifier_Letter", "Lo", "Other_Letter",
+    "Lt", "Titlecase_Letter", "Lu", "Uppercase_Letter", "M", "Mark", "Mc", "Spacing_Mark", "Me", "Enclosing_Mark",
+    "Mn", "Nonspacing_Mark", "N", "Number", "Nd", "Decimal_Number", "Nl", "Letter_Number", "No", "Other_Number",
+    "P", "Punctuation", "Pc", "Connector_Punctuation", "Pd", "Dash_Punctuation", "Pe", "Close_Punctuation",
+    "Pf", "Final_Punctuation", "Pi", "Initial_Punctuation", "Po", "Other_Punctuation", "Ps", "Open_Punctuation",
+    "S", "Symbol", "Sc", "Currency_Symbol", "Sk", "Modifier_Symbol", "Sm", "Math_Symbol", "So", "Other_Symbol",
+    "Z", "Separator", "Zl", "Line_Separator", "Zp", "Paragraph_Separator", "Zs", "Space_Separator",
+];
+
+const BINARY_PROPERTIES: &[&str] = &[
+    "ASCII", "ASCII_Hex_Digit", "Alphabetic", "Any", "Assigned", "Bidi_Control", "Bidi_Mirrored",
+    "Case_Ignorable", "Cased", "Changes_When_Casefolded", "Changes_When_Casemapped", "Changes_When_Lowercased",
+    "Changes_When_NFKC_Casefolded", "Changes_When_Titlecased", "Changes_When_Uppercased", "Dash",
+    "Default_Ignorable_Code_Point", "Deprecated", "Diacritic", "Emoji", "Emoji_Component", "Emoji_Modifier",
+    "Emoji_Modifier_Base", "Emoji_Presentation", "Extended_Pictographic", "Extender", "Grapheme_Base",
+    "Grapheme_Extend", "Hex_Digit", "IDS_Binary_Operator", "IDS_Trinary_Operator", "ID_Continue", "ID_Start",
+    "Ideographic", "Join_Control", "Logical_Order_Exception", "Lowercase", "Math", "Noncharacter_Code_Point",
+    "Pattern_Syntax", "Pattern_White_Space", "Quotation_Mark", "Radical", "Regional_Indicator",
+    "Sentence_Terminal", "Soft_Dotted", "Terminal_Punctuation", "Unified_Ideograph", "Uppercase",
+    "Variation_Selector", "White_Space", "XID_Continue", "XID_Start",
+];
+
+/// Translate a fancy-regex pattern for another engine, erroring with every feature it lacks
+///
+/// Classes keep their Unicode meaning, e.g. `\d` becomes `\p{Nd}` in JavaScript, and `.` never
+/// matches a line feed, as in fancy-regex.
+pub(crate) fn translate(pattern: &str, engine: Engine) -> Result<String, Vec<RegexFeature>> {
+    let mut translator = Translator {
+        chars: pattern.chars().collect(),
+        pos: 0,
+        engine,
+        regex: String::new(),
+        features: vec![],
+        groups: 0,
+        group_names: vec![],
+    };
+
+    while translator.pos < translator.chars.len() {
+        translator.token();
+    }
+
+    if !translator.features.is_empty() {
+        return Err(translator.features);
+    }
+
+    Ok(translator.regex)
+}
+
+struct Translator {
+    chars: Vec<char>,
+    pos: usize,
+    engine: Engine,
+    regex: String,
+    features: Vec<RegexFeature>,
+    groups: usize,
+    group_names: Vec<(String, usize)>,
+}
+
+impl Translator {
+    fn found(&mut self, feature: RegexFeature) {
+        if !self.features.contains(&feature) {
+            self.features.push(feature);
+        }
+    }
+
+    fn peek(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    fn starts_with(&self, prefix: &str) -> bool {
+        self.chars[self.pos..].iter().copied().take(prefix.chars().count()).eq(prefix.chars())
+    }
+
+    /// Take the characters up to the terminator, skipping it
+    fn take_until(&mut self, terminator: char) -> String {
+        let taken: String = self.chars[self.pos..].iter().take_while(|c| **c != terminator).collect();
+
+        self.pos = (self.pos + taken.chars().count() + 1).min(self.chars.len());
+        taken
+    }
+
+    fn token(&mut self) {
+        let c = self.chars[self.pos];
+
+        match c {
+            '\\' => self.escape(false),
+            '[' => self.class(),
+            '(' => self.group(),
+            '.' => {
+                self.pos += 1;
+                self.regex.push_str(r"[^\n]");
+            }
+            '*' | '+' | '?' => {
+                self.pos += 1;
+                self.regex.push(c);
+                self.quantifier_suffix();
+            }
+            '{' if self.repetition() => {}
+            '{' | '}' | ']' => {
+                self.pos += 1;
+                self.regex.push('\\');
+                self.regex.push(c);
+            }
+            _ => {
+                self.pos += 1;
+                self.literal(c);
+            }
+        }
+    }
+
+    /// A bounded repetition like `{2,5}`, false if the brace is a literal
+    fn repetition(&mut self) -> bool {
+        let body: String = self.chars[self.pos + 1..].iter().take_while(|c| **c != '}').collect();
+        let counts: Vec<&str> = body.split(',').collect();
+
+        let valid = self.pos + 1 + body.chars().count() < self.chars.len()
+            && counts.len() <= 2
+            && !counts[0].is_empty()
+            && counts.iter().all(|n| n.chars().all(|c| c.is_ascii_digit()));
+
+        if !valid {
+            return false;
+        }
+
+        if self.engine == Engine::Postgres && counts.iter().any(|n| n.parse::<u32>().map_or(!n.is_empty(), |n| n > 255)) {
+            self.found(RegexFeature::RepetitionLimit);
+        }
+
+        self.pos += body.chars().count() + 2;
+        self.regex.push_str(&format!("{{{}}}", body));
+        self.quantifier_suffix();
+        true
+    }
+
+    fn quantifier_suffix(&mut self) {
+        match self.peek(0) {
+            Some('?') => {
+                self.pos += 1;
+                self.regex.push('?');
+            }
+            Some('+') => {
+                self.pos += 1;
+                self.found(RegexFeature::PossessiveQuantifier);
+            }
+            _ => {}
+        }
+    }
+
+    fn group(&mut self) {
+        if self.starts_with("(?P<") || (self.starts_with("(?<") && !self.starts_with("(?<=") && !self.starts_with("(?<!")) {
+            self.pos += if self.starts_with("(?P<") { 4 } else { 3 };
+            let name = self.take_until('>');
+
+            self.groups += 1;
+            self.group_names.push((name.clone(), self.groups));
+
+            match self.engine {
+                Engine::JavaScript => self.regex.push_str(&format!("(?<{}>", name)),
+                Engine::Postgres => self.regex.push('('),
+            }
+        } else if self.starts_with("(?P=") {
+            self.pos += 4;
+            let name = self.take_until(')');
+
+            self.backreference(&name);
+        } else if self.starts_with("(?<=") || self.starts_with("(?<!") {
+            if self.engine == Engine::JavaScript {
+                self.found(RegexFeature::Lookbehind);
+            }
+
+            self.regex.extend(&self.chars[self.pos..self.pos + 4]);
+            self.pos += 4;
+        } else if self.starts_with("(?>") {
+            self.found(RegexFeature::AtomicGroup);
+            self.pos += 3;
+        } else if self.starts_with("(?:") || self.starts_with("(?=") || self.starts_with("(?!") {
+            self.regex.extend(&self.chars[self.pos..self.pos + 3]);
+            self.pos += 3;
+        } else if self.starts_with("(?") {
+            self.found(RegexFeature::InlineFlags);
+            self.pos += 2;
+        } else {
+            self.groups += 1;
+            self.pos += 1;
+            self.regex.push('(');
+        }
+    }
+
+    fn backreference(&mut self, name: &str) {
+        match self.engine {
+            Engine::JavaScript => self.regex.push_str(&format!("\\k<{}>", name)),
+            Engine::Postgres => {
+                let index = self.group_names.iter().find(|(n, _)| n == name).map_or(0, |(_, i)| *i);
+
+                self.regex.push_str(&format!("\\{}", index));
+            }
+        }
+    }
+
+    fn class(&mut self) {
+        self.pos += 1;
+        self.regex.push('[');
+
+        if self.peek(0) == Some('^') {
+            self.pos += 1;
+            self.regex.push('^');
+        }
+
+        if self.peek(0) == Some(']') {
+            self.pos += 1;
+            self.regex.push_str("\\]");
+        }
+
+        while let Some(c) = self.peek(0) {
+            match c {
+                ']' => {
+                    self.pos += 1;
+                    self.regex.push(']');
+                    return;
+                }
+                '[' if self.starts_with("[:") => {
+                    self.found(RegexFeature::PosixClass);
+                    self.take_until(']');
+                }
+                '[' => {
+                    self.found(RegexFeature::ClassSetOperation);
+                    self.pos += 1;
+                }
+                '&' | '-' | '~' if self.peek(1) == Some(c) => {
+                    self.found(RegexFeature::ClassSetOperation);
+                    self.pos += 2;
+                }
+                '\\' => self.escape(true),
+                _ => {
+                    self.pos += 1;
+                    self.literal(c);
+                }
+            }
+        }
+    }
+
+    fn escape(&mut self, in_class: bool) {
+        let c = match self.peek(1) {
+            Some(c) => c,
+            None => {
+                self.pos += 1;
+                self.found(RegexFeature::Escape);
+                return;
+            }
+        };
+
+        self.pos += 2;
+
+        match (c, self.engine) {
+            ('d' | 'D' | 's' | 'S' | 'w' | 'W', Engine::Postgres) => self.found(RegexFeature::UnicodeClass),
+            ('d', _) => self.regex.push_str(r"\p{Nd}"),
+            ('D', _) => self.regex.push_str(r"\P{Nd}"),
+            ('s', _) => self.regex.push_str(r"\p{White_Space}"),
+            ('S', _) => self.regex.push_str(r"\P{White_Space}"),
+            ('w', _) if in_class => self.regex.push_str(WORD),
+            ('w', _) => self.regex.push_str(&format!("[{}]", WORD)),
+            ('W', _) if in_class => self.found(RegexFeature::ClassSetOperation),
+            ('W', _) => self.regex.push_str(&format!("[^{}]", WORD)),
+            ('p' | 'P', _) => self.property(c == 'P'),
+            ('b' | 'B' | '<' | '>', _) if !in_class => {
+                self.found(RegexFeature::WordBoundary);
+
+                if self.peek(0) == Some('{') {
+                    self.take_until('}');
+                }
+            }
+            ('A', _) if !in_class => self.regex.push('^'),
+            ('z', _) if !in_class => self.regex.push('$'),
+            ('x' | 'u' | 'U', _) => self.code_point(c),
+            ('a', _) => self.push_code_point(0x07),
+            ('e', _) => self.push_code_point(0x1B),
+            ('n' | 't' | 'r' | 'f' | 'v', _) => {
+                self.regex.push('\\');
+                self.regex.push(c);
+            }
+            ('1'..='9', _) if !in_class => {
+                self.regex.push('\\');
+                self.regex.push(c);
+            }
+            ('k', _) if !in_class && self.peek(0) == Some('<') => {
+                self.pos += 1;
+                let name = self.take_until('>');
+
+                self.backreference(&name);
+            }
+            (c, _) if c.is_ascii_alphanumeric() => self.found(RegexFeature::Escape),
+            (c, Engine::JavaScript) if "^$\\.*+?()[]{}|/".contains(c) || (in_class && c == '-') => {
+                self.regex.push('\\');
+                self.regex.push(c);
+            }
+            (c, Engine::JavaScript) => self.literal(c),
+            (c, Engine::Postgres) => {
+                self.regex.push('\\');
+                self.regex.push(c);
+            }
+        }
+    }
+
+    /// `\x41`, `\x{1F43A}`, `\u00E9`, `\u{E9}`, `\U0001F43A` and `\U{1F43A}`
+    fn code_point(&mut self, c: char) {
+        let digits = match c {
+            'x' => 2,
+            'u' => 4,
+            _ => 8,
+        };
+
+        let hex: String = if self.peek(0) == Some('{') {
+            self.pos += 1;
+            self.take_until('}')
+        } else {
+            let hex: String = self.chars[self.pos..].iter().take(digits).collect();
+
+            self.pos += hex.chars().count();
+            hex
+        };
+
+        match u32::from_str_radix(&hex, 16) {
+            Ok(code_point) => self.push_code_point(code_point),
+            Err(_) => self.found(RegexFeature::Escape),
+        }
+    }
+
+    fn push_code_point(&mut self, code_point: u32) {
+        match self.engine {
+            Engine::JavaScript => self.regex.push_str(&format!("\\u{{{:X}}}", code_point)),
+            Engine::Postgres => self.regex.push_str(&format!("\\U{:08X}", code_point)),
+        }
+    }
+
+    /// `\pL`, `\p{Greek}`, `\p{sc=Greek}` and their negations
+    fn property(&mut self, mut negated: bool) {
+        let name = if self.peek(0) == Some('{') {
+            self.pos += 1;
+            self.take_until('}')
+        } else {
+            self.pos += 1;
+            self.chars.get(self.pos - 1).map_or(String::new(), |c| c.to_string())
+        };
+
+        if self.engine == Engine::Postgres {
+            self.found(RegexFeature::UnicodeClass);
+            return;
+        }
+
+        let mut name = name.trim();
+
+        if let Some(rest) = name.strip_prefix('^') {
+            negated = !negated;
+            name = rest;
+        }
+
+        let property = match name.split_once("!=").or_else(|| name.split_once('=')).or_else(|| name.split_once(':')) {
+            Some((key, value)) => {
+                if name.contains("!=") {
+                    negated = !negated;
+                }
+
+                match (key.trim(), value.trim()) {
+                    ("sc" | "Script", value) if is_script(value) => Some(format!("Script={}", value)),
+                    ("scx" | "Script_Extensions", value) if is_script(value) => {
+                        Some(format!("Script_Extensions={}", value))
+                    }
+                    ("gc" | "General_Category", value) if GENERAL_CATEGORIES.contains(&value) => {
+                        Some(value.to_string())
+                    }
+                    _ => None,
+                }
+            }
+            None if GENERAL_CATEGORIES.contains(&name) || BINARY_PROPERTIES.contains(&name) => Some(name.to_string()),
+            None if is_script(name) => Some(format!("Script={}", name)),
+            None => None,
+        };
+
+        match property {
+            Some(property) => self.regex.push_str(&format!("\\{}{{{}}}", if negated { 'P' } else { 'p' }, property)),
+            None => self.found(RegexFeature::UnicodeClass),
+        }
+    }
+
+    fn literal(&mut self, c: char) {
+        match self.engine {
+            Engine::JavaScript if c == '/' => self.regex.push_str("\\/"),
+            Engine::JavaScript if matches!(c, '\n' | '\r' | '\u{2028}' | '\u{2029}') => {
+                self.push_code_point(c as u32)
+            }
+            _ => self.regex.push(c),
+        }
+    }
+}
+
+/// Whether the name is spelled like a script in JavaScript, e.g. `Greek` or `Old_Italic`
+///
+/// fancy-regex matches property names loosely, so `greek` is valid there but not in JavaScript.
+fn is_script(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .split('_')
+            .all(|word| word.starts_with(|c: char| c.is_ascii_uppercase()) && word.chars().all(|c| c.is_ascii_alphabetic()))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::regex_compat::RegexFeature::{
+        AtomicGroup, ClassSetOperation, Escape, InlineFlags, Lookbehind, PossessiveQuantifier, RepetitionLimit,
+        UnicodeClass, WordBoundary,
+    };
+    use crate::regex_compat::{translate, Engine};
+
+    #[test]
+    fn it_keeps_the_unicode_meaning_of_classes_in_javascript() {
+        assert_eq!(translate(r"^\d+$", Engine::JavaScript), Ok(r"^\p{Nd}+$".to_string()));
+        assert_eq!(translate(r"^\s\S$", Engine::JavaScript), Ok(r"^\p{White_Space}\P{White_Space}$".to_string()));
+        assert_eq!(
+            translate(r"^\w[\w-]*$", Engine::JavaScript),
+            Ok(r"^[\p{Alphabetic}\p{M}\p{Nd}\p{Pc}\p{Join_Control}][\p{Alphabetic}\p{M}\p{Nd}\p{Pc}\p{Join_Control}-]*$".to_string())
+        );
+        assert_eq!(translate(r"^.+$", Engine::JavaScript), Ok(r"^[^\n]+$".to_string()));
+        assert_eq!(translate(r"\A[a.]\z", Engine::JavaScript), Ok(r"^[a.]$".to_string()));
+    }
+
+    #[test]
+    fn it_translates_escapes_and_properties_to_javascript() {
+        assert_eq!(translate(r"^\x{1F43A}\x41é$", Engine::JavaScript), Ok(r"^\u{1F43A}\u{41}é$".to_string()));
+        assert_eq!(translate(r"^\pL\PN$", Engine::JavaScript), Ok(r"^\p{L}\P{N}$".to_string()));
+        assert_eq!(
+            translate(r"^\p{Greek}\p{sc=Latin}\p{^Lu}$", Engine::JavaScript),
+            Ok(r"^\p{Script=Greek}\p{Script=Latin}\P{Lu}$".to_string())
+        );
+        assert_eq!(translate(r"^\#\-[\-#]$", Engine::JavaScript), Ok(r"^#-[\-#]$".to_string()));
+        assert_eq!(translate(r"^a{2,3}?}$", Engine::JavaScript), Ok(r"^a{2,3}?\}$".to_string()));
+        assert_eq!(translate(r"^(?P<c>a)(?P=c)\k<c>$", Engine::JavaScript), Ok(r"^(?<c>a)\k<c>\k<c>$".to_string()));
+    }
+
+    #[test]
+    fn it_flags_features_javascript_lacks() {
+        assert_eq!(
+            translate(r"(?<!_)\w++(?>a|b)(?i)", Engine::JavaScript),
+            Err(vec![Lookbehind, PossessiveQuantifier, AtomicGroup, InlineFlags])
+        );
+        assert_eq!(translate(r"\bword\B", Engine::JavaScript), Err(vec![WordBoundary]));
+        assert_eq!(translate(r"[a[bc]][\w--\d][a&&b][\W]", Engine::JavaScript), Err(vec![ClassSetOperation]));
+        assert_eq!(translate(r"\p{greek}\p{age=3.0}", Engine::JavaScript), Err(vec![UnicodeClass]));
+        assert_eq!(translate(r"\Q", Engine::JavaScript), Err(vec![Escape]));
+    }
+
+    #[test]
+    fn it_translates_a_pattern_to_postgres() {
+        assert_eq!(translate(r"^[a-z'\-]+(?<!-)$", Engine::Postgres), Ok(r"^[a-z'\-]+(?<!-)$".to_string()));
+        assert_eq!(translate(r"^(?P<c>.)(?P=c)\x41$", Engine::Postgres), Ok(r"^([^\n])\1\U00000041$".to_string()));
+        assert_eq!(translate(r"^\d{1,3}\p{L}$", Engine::Postgres), Err(vec![UnicodeClass]));
+        assert_eq!(translate(r"^a{1,256}$", Engine::Postgres), Err(vec![RepetitionLimit]));
+    }
+}
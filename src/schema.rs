@@ -0,0 +1,232 @@
+//! JSON Schema generation
+
+#![deny(missing_docs)]
+
+use schemars::Schema;
+use serde_json::{json, Map, Number, Value};
+
+use crate::constraint::{BaseType, Bound, ConstraintSet};
+use crate::regex_compat::{translate, Engine, RegexFeature};
+
+#[doc(hidden)]
+pub mod __private {
+    pub use schemars::{JsonSchema, Schema, SchemaGenerator};
+}
+
+/// The JSON Schema of the constraints, e.g. `{"type": "integer", "minimum": 1, "maximum": 1000}`
+///
+/// Optional values, as built by [`new_string_option`](crate::string_option::new_string_option),
+/// also allow `null`. Patterns are translated to ECMA-262 with the `u` flag in mind and left out
+/// when that fails, see [`unsupported`]. Sensitive values
+/// are marked `writeOnly`, so they are accepted in requests but never documented in responses.
+pub fn json_schema(constraints: &ConstraintSet) -> Schema {
+    let type_name = match constraints.base_type {
+        BaseType::Integer => "integer",
+        BaseType::Float => "number",
+        BaseType::String => "string",
+    };
+
+    let mut schema = Map::new();
+
+    if constraints.optional {
+        schema.insert("type".to_string(), json!([type_name, "null"]));
+    } else {
+        schema.insert("type".to_string(), json!(type_name));
+    }
+
+    if let Some(min) = constraints.min.and_then(number) {
+        schema.insert("minimum".to_string(), min);
+    }
+
+    if let Some(max) = constraints.max.and_then(number) {
+        schema.insert("maximum".to_string(), max);
+    }
+
+    if let Some(min_len) = constraints.min_len {
+        schema.insert("minLength".to_string(), json!(min_len));
+    }
+
+    if let Some(max_len) = constraints.max_len {
+        schema.insert("maxLength".to_string(), json!(max_len));
+    }
+
+    if let Some(pattern) = constraints.pattern.as_deref().and_then(|p| translate(p, Engine::JavaScript).ok()) {
+        schema.insert("pattern".to_string(), json!(pattern));
+    }
+
+    if let Some(description) = &constraints.description {
+        schema.insert("description".to_string(), json!(description));
+    }
+
+    if constraints.sensitive {
+        schema.insert("writeOnly".to_string(), json!(true));
+    }
+
+    Schema::from(schema)
+}
+
+/// The features of the pattern ECMA-262 lacks, which leave the pattern out of the JSON Schema
+pub fn unsupported(constraints: &ConstraintSet) -> Vec<RegexFeature> {
+    match &constraints.pattern {
+        Some(pattern) => translate(pattern, Engine::JavaScript).err().unwrap_or_default(),
+        None => vec![],
+    }
+}
+
+/// Bounds which are no JSON number, like infinity, are left out
+fn number(bound: Bound) -> Option<Value> {
+    match bound {
+        Bound::Int(val) => Some(json!(val)),
+        Bound::UInt(val) => Some(json!(val)),
+        Bound::Float(val) => Number::from_f64(val).map(Value::Number),
+    }
+}
+
+/// Implement schemars `JsonSchema` for a constrained type from its [`ConstraintSet`]
+///
+/// The schema is named after the type, so it becomes a reusable component in OpenAPI documents.
+/// Without constraints, those of its [`Constraints`](crate::constraint::Constraints) implementation are used.
+///
+/// ```ignore
+/// impl_json_schema!(Handle, ConstraintSet::string(63).with_pattern(&handle_pattern()));
+/// impl_json_schema!(Quantity);
+/// ```
+#[macro_export]
+macro_rules! impl_json_schema {
+    ($ty:ty) => {
+        $crate::impl_json_schema!($ty, <$ty as $crate::constraint::Constraints>::constraints());
+    };
+    ($ty:ty, $constraints:expr) => {
+        impl $crate::schema::__private::JsonSchema for $ty {
+            fn schema_name() -> ::std::borrow::Cow<'static, str> {
+                ::std::borrow::Cow::Borrowed(stringify!($ty))
+            }
+
+            fn schema_id() -> ::std::borrow::Cow<'static, str> {
+                ::std::borrow::Cow::Borrowed(concat!(module_path!(), "::", stringify!($ty)))
+            }
+
+            fn json_schema(_: &mut $crate::schema::__private::SchemaGenerator) -> $crate::schema::__private::Schema {
+                $crate::schema::json_schema(&$constraints)
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use fancy_regex::Regex;
+    use schemars::SchemaGenerator;
+    use serde_json::json;
+
+    use crate::constraint::ConstraintSet;
+    use crate::regex_compat::RegexFeature;
+    use crate::schema::{json_schema, unsupported};
+    use crate::string_like::Pattern;
+
+    mod handle {
+        use fancy_regex::Regex;
+
+        use crate::constraint::ConstraintSet;
+        use crate::string_like::Pattern;
+
+        pub struct Handle;
+
+        pub fn pattern() -> Pattern {
+            Pattern::new(Regex::new(r"^[a-z][a-z0-9\-]*$").unwrap()).with_description("a lower-case handle")
+        }
+
+        crate::impl_json_schema!(Handle, ConstraintSet::string(63).with_pattern(&pattern()));
+    }
+
+    mod quantity {
+        use crate::constraint::{ConstraintSet, Constraints};
+
+        pub struct Quantity;
+
+        impl Constraints for Quantity {
+            fn constraints() -> ConstraintSet {
+                ConstraintSet::int(1u16, 1000)
+            }
+        }
+
+        crate::impl_json_schema!(Quantity);
+    }
+
+    #[test]
+    fn it_describes_the_bounds() {
+        assert_eq!(
+            json_schema(&ConstraintSet::int(1u16, 1000)),
+            json!({"type": "integer", "minimum": 1, "maximum": 1000})
+        );
+
+        assert_eq!(
+            json_schema(&ConstraintSet::float(0.0, 0.5)),
+            json!({"type": "number", "minimum": 0.0, "maximum": 0.5})
+        );
+
+        assert_eq!(
+            json_schema(&ConstraintSet::string_option(280)),
+            json!({"type": ["string", "null"], "maxLength": 280})
+        );
+    }
+
+    #[test]
+    fn it_translates_the_pattern_to_ecma_262() {
+        let word = Pattern::new(Regex::new(r"^\d+.$").unwrap());
+        let slug = Pattern::new(Regex::new(r"^(?<!-)[a-z\-]+$").unwrap());
+
+        assert_eq!(
+            json_schema(&ConstraintSet::string_like(&word)),
+            json!({"type": "string", "pattern": r"^\p{Nd}+[^\n]$"})
+        );
+
+        assert_eq!(json_schema(&ConstraintSet::string_like(&slug)), json!({"type": "string"}));
+        assert_eq!(unsupported(&ConstraintSet::string_like(&slug)), vec![RegexFeature::Lookbehind]);
+        assert_eq!(unsupported(&ConstraintSet::string_like(&word)), vec![]);
+    }
+
+    #[test]
+    fn it_marks_a_sensitive_value_write_only() {
+        assert_eq!(
+            json_schema(&ConstraintSet::string(64).secret()),
+            json!({"type": "string", "minLength": 1, "maxLength": 64, "writeOnly": true})
+        );
+    }
+
+    #[test]
+    fn it_registers_a_constrained_type_as_component() {
+        let mut generator = SchemaGenerator::default();
+
+        assert_eq!(
+            generator.subschema_for::<handle::Handle>(),
+            json!({"$ref": "#/$defs/Handle"})
+        );
+
+        assert_eq!(
+            generator.definitions().get("Handle"),
+            Some(&json!({
+                "type": "string",
+                "minLength": 1,
+                "maxLength": 63,
+                "pattern": r"^[a-z][a-z0-9\-]*$",
+                "description": "a lower-case handle",
+            }))
+        );
+    }
+
+    #[test]
+    fn it_registers_a_type_from_its_constraints() {
+        let mut generator = SchemaGenerator::default();
+
+        assert_eq!(
+            generator.subschema_for::<quantity::Quantity>(),
+            json!({"$ref": "#/$defs/Quantity"})
+        );
+
+        assert_eq!(
+            generator.definitions().get("Quantity"),
+            Some(&json!({"type": "integer", "minimum": 1, "maximum": 1000}))
+        );
+    }
+}
@@ -0,0 +1,258 @@
+//! Secret values
+
+#![deny(missing_docs)]
+
+use core::fmt;
+
+use subtle::ConstantTimeEq;
+use zeroize::Zeroize;
+
+use crate::error::{ConstrainedTypeError, ConstrainedTypeResult};
+use crate::string::new_string;
+use crate::string_like::{new_string_like, Pattern};
+
+/// The value shown in place of a secret in errors
+pub const REDACTED: &str = "<redacted>";
+
+const MASK: &str = "*******";
+
+/// A wrapper for sensitive values which can be read at most once
+///
+/// The value is zeroized when the secret is dropped unconsumed, it is masked when formatted
+/// and the wrapper is deliberately not `Clone`, so the value cannot be duplicated by accident.
+///
+/// Secrets are compared in constant time, so a value object deriving `PartialEq` on top of
+/// a secret does so too. A consumed secret equals no secret, not even itself, so two consumed
+/// secrets never compare as a match. Like `f64` it is therefore `PartialEq` but not `Eq`.
+pub struct Secret<T: Zeroize> {
+    inner: Option<T>,
+}
+
+impl<T: Zeroize> Secret<T> {
+    /// Wrap a sensitive value
+    pub fn new(value: T) -> Self {
+        Self { inner: Some(value) }
+    }
+
+    /// Borrow the value without consuming it, `None` if it has already been consumed
+    pub fn expose(&self) -> Option<&T> {
+        self.inner.as_ref()
+    }
+
+    /// Take the value out of the secret, `None` if it has already been consumed
+    pub fn consume(&mut self) -> Option<T> {
+        self.inner.take()
+    }
+
+    /// Whether the value has already been consumed
+    pub fn is_consumed(&self) -> bool {
+        self.inner.is_none()
+    }
+}
+
+impl<T: Zeroize + AsRef<[u8]>> PartialEq for Secret<T> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.expose(), other.expose()) {
+            (Some(a), Some(b)) => constant_time_eq(a.as_ref(), b.as_ref()),
+            _ => false,
+        }
+    }
+}
+
+impl<T: Zeroize> Drop for Secret<T> {
+    fn drop(&mut self) {
+        self.inner.zeroize();
+    }
+}
+
+impl<T: Zeroize> fmt::Debug for Secret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "\"{}\"", MASK)
+    }
+}
+
+impl<T: Zeroize> fmt::Display for Secret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", MASK)
+    }
+}
+
+/// Compare two byte strings in constant time with respect to their content
+///
+/// Only the length may leak through timing, so use it to implement `PartialEq` for any value object
+/// holding sensitive data.
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.ct_eq(b).into()
+}
+
+/// A builder function constraining a secret String to be not empty and neither exceeding a character limit
+///
+/// The error is marked [`sensitive`](ConstrainedTypeError::sensitive).
+pub fn new_secret_string<T, F>(
+    field_name: &str,
+    ctor: F,
+    max_len: usize,
+    val: &str,
+) -> ConstrainedTypeResult<T>
+    where
+        F: Fn(Secret<String>) -> T,
+{
+    new_string(field_name, |v| ctor(Secret::new(v.to_string())), max_len, val).map_err(ConstrainedTypeError::sensitive)
+}
+
+/// A builder function constraining a secret String to match a given pattern,
+/// always redacting the value in the error and marking it [`sensitive`](ConstrainedTypeError::sensitive)
+pub fn new_secret_string_like<T, F, P>(
+    field_name: &str,
+    ctor: F,
+    pattern: P,
+    val: &str,
+) -> ConstrainedTypeResult<T>
+    where
+        F: Fn(Secret<String>) -> T,
+        P: Into<Pattern>,
+{
+    new_string_like(field_name, |v| ctor(Secret::new(v.to_string())), pattern, val, Some(REDACTED))
+        .map_err(ConstrainedTypeError::sensitive)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::ConstrainedTypeErrorKind::{InvalidMaxLen, InvalidPattern};
+    use crate::error::{ConstrainedTypeError, Span};
+    use crate::secret::{constant_time_eq, REDACTED};
+
+    mod api_key {
+        use crate::error::ConstrainedTypeResult;
+        use crate::secret::{new_secret_string, new_secret_string_like, Secret};
+        use fancy_regex::Regex;
+
+        pub(crate) const PATTERN: &str = r"^key_[a-z0-9]+$";
+
+        #[derive(Debug, PartialEq)]
+        pub struct ApiKey(pub(crate) Secret<String>);
+
+        impl ApiKey {
+            pub(crate) fn new(secret: Secret<String>) -> ApiKey {
+                Self(secret)
+            }
+
+            pub fn value(&mut self) -> Option<String> {
+                self.0.consume()
+            }
+        }
+
+        pub fn new(field_name: &str, raw: &str) -> ConstrainedTypeResult<ApiKey> {
+            new_secret_string_like(field_name, ApiKey::new, Regex::new(PATTERN).unwrap(), raw)
+        }
+
+        pub fn new_short(field_name: &str, raw: &str) -> ConstrainedTypeResult<ApiKey> {
+            new_secret_string(field_name, ApiKey::new, 8, raw)
+        }
+    }
+
+    #[test]
+    fn it_always_redacts_the_value_in_errors() {
+        assert_eq!(
+            api_key::new("api_key", "key_ABC").unwrap_err(),
+            ConstrainedTypeError::from(InvalidPattern {
+                field_name: "api_key".to_string(),
+                expected: api_key::PATTERN.to_string(),
+                found: REDACTED.to_string(),
+            })
+        );
+
+        assert_eq!(
+            api_key::new_short("api_key", "key_abc123").unwrap_err(),
+            ConstrainedTypeError::from(InvalidMaxLen {
+                field_name: "api_key".to_string(),
+                expected: (8).to_string(),
+                found: (10).to_string(),
+            })
+        );
+
+        assert_eq!(
+            api_key::new_short("api_key", "key_abc123").unwrap_err().span(),
+            Some(Span { start: 8, end: 10, char_start: 8 })
+        );
+    }
+
+    #[test]
+    fn it_marks_errors_as_sensitive() {
+        assert!(api_key::new("api_key", "key_ABC").unwrap_err().is_sensitive());
+        assert!(api_key::new_short("api_key", "key_abc123").unwrap_err().is_sensitive());
+    }
+
+    #[test]
+    fn it_redacts_the_message_of_sensitive_errors() {
+        let err = ConstrainedTypeError::from(InvalidPattern {
+            field_name: "api_key".to_string(),
+            expected: api_key::PATTERN.to_string(),
+            found: "key_SECRET".to_string(),
+        }).with_description("an API key").sensitive();
+
+        assert_eq!(err.to_redacted_string(), "\"api_key\" must be an API key for value \"<redacted>\"");
+
+        let err = ConstrainedTypeError::multiple(vec![
+            api_key::new_short("api_key", "key_abc123").unwrap_err(),
+            ConstrainedTypeError::from(InvalidMaxLen {
+                field_name: "name".to_string(),
+                expected: (5).to_string(),
+                found: (6).to_string(),
+            }),
+        ]);
+
+        assert_eq!(
+            err.to_redacted_string(),
+            "\"api_key\" must not be greater than \"8\" characters, \"<redacted>\"; \
+             \"name\" must not be greater than \"5\" characters, \"6\""
+        );
+    }
+
+    #[test]
+    fn it_can_be_consumed_once() {
+        let mut key = api_key::new("api_key", "key_abc123").unwrap();
+
+        assert_eq!(key.value(), Some("key_abc123".to_string()));
+        assert_eq!(key.value(), None);
+    }
+
+    #[test]
+    fn it_masks_the_value_when_formatted() {
+        let key = api_key::new("api_key", "key_abc123").unwrap();
+
+        assert_eq!(format!("{:?}", key), "ApiKey(\"*******\")");
+        assert_eq!(format!("{}", key.0), "*******");
+        assert_eq!(key.0.expose(), Some(&"key_abc123".to_string()));
+    }
+
+    #[test]
+    fn it_compares_the_values() {
+        assert_eq!(api_key::new("api_key", "key_abc123").unwrap(), api_key::new("api_key", "key_abc123").unwrap());
+        assert_ne!(api_key::new("api_key", "key_abc123").unwrap(), api_key::new("api_key", "key_abc124").unwrap());
+        assert_ne!(api_key::new("api_key", "key_abc123").unwrap(), api_key::new("api_key", "key_abc1234").unwrap());
+    }
+
+    #[test]
+    fn it_never_matches_a_consumed_secret() {
+        let mut consumed = api_key::new("api_key", "key_abc123").unwrap();
+        let mut other = api_key::new("api_key", "key_abc123").unwrap();
+
+        consumed.value();
+
+        assert_ne!(consumed, api_key::new("api_key", "key_abc123").unwrap());
+        assert_ne!(api_key::new("api_key", "key_abc123").unwrap(), consumed);
+
+        other.value();
+
+        assert_ne!(consumed, other);
+    }
+
+    #[test]
+    fn it_compares_byte_strings() {
+        assert!(constant_time_eq(b"key_abc123", b"key_abc123"));
+        assert!(!constant_time_eq(b"key_abc123", b"key_abc124"));
+        assert!(!constant_time_eq(b"key_abc123", b"key_abc1234"));
+        assert!(constant_time_eq(b"", b""));
+    }
+}
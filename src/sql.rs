@@ -0,0 +1,235 @@
+//! SQL column definitions
+
+#![deny(missing_docs)]
+
+use crate::constraint::{BaseType, Bound, ConstraintSet};
+use crate::regex_compat::{translate, Engine, RegexFeature};
+
+/// The longest `VARCHAR` PostgreSQL allows
+const MAX_VARCHAR_LEN: usize = 10_485_760;
+
+/// The SQL dialect to generate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dialect {
+    /// PostgreSQL
+    Postgres,
+    /// SQLite, which has no built-in regex operator, so patterns are not checked
+    Sqlite,
+}
+
+/// The column type of the constraints, e.g. `VARCHAR(63)` or `INTEGER`
+///
+/// Postgres integers widen to `BIGINT` or `NUMERIC` when the bounds do not fit an `INTEGER`.
+/// Strings without a limit Postgres accepts for `VARCHAR`, like 0, become `TEXT`.
+pub fn column_type(constraints: &ConstraintSet, dialect: Dialect) -> String {
+    match (constraints.base_type, dialect) {
+        (BaseType::Integer, Dialect::Postgres) => {
+            let fits = |bound: Option<Bound>, range: (i128, i128)| {
+                let bound = match bound {
+                    Some(Bound::Int(b)) => i128::from(b),
+                    Some(Bound::UInt(b)) => i128::from(b),
+                    _ => return false,
+                };
+
+                bound >= range.0 && bound <= range.1
+            };
+            let fits_both = |range: (i128, i128)| fits(constraints.min, range) && fits(constraints.max, range);
+
+            if fits_both((i32::MIN.into(), i32::MAX.into())) {
+                "INTEGER".to_string()
+            } else if fits_both((i64::MIN.into(), i64::MAX.into())) {
+                "BIGINT".to_string()
+            } else {
+                "NUMERIC".to_string()
+            }
+        }
+        (BaseType::Integer, Dialect::Sqlite) => "INTEGER".to_string(),
+        (BaseType::Float, Dialect::Postgres) => "DOUBLE PRECISION".to_string(),
+        (BaseType::Float, Dialect::Sqlite) => "REAL".to_string(),
+        (BaseType::String, Dialect::Postgres) => match constraints.max_len {
+            Some(max_len) if max_len > 0 && max_len <= MAX_VARCHAR_LEN => format!("VARCHAR({})", max_len),
+            _ => "TEXT".to_string(),
+        },
+        (BaseType::String, Dialect::Sqlite) => "TEXT".to_string(),
+    }
+}
+
+/// The CHECK clause enforcing the constraints on a column, if there is anything to check
+///
+/// Infinite bounds are left out, as is a pattern the dialect cannot express, see [`unsupported`].
+pub fn check_constraint(column: &str, constraints: &ConstraintSet, dialect: Dialect) -> Option<String> {
+    let column = quote_identifier(column);
+    let mut conditions = vec![];
+
+    let min = constraints.min.filter(Bound::is_finite).map(|b| b.to_string());
+    let max = constraints.max.filter(Bound::is_finite).map(|b| b.to_string());
+
+    if let Some(condition) = between(&column, min.as_deref(), max.as_deref()) {
+        conditions.push(condition);
+    }
+
+    let length = match dialect {
+        Dialect::Postgres => format!("char_length({})", column),
+        Dialect::Sqlite => format!("length({})", column),
+    };
+    let min_len = constraints.min_len.map(|l| l.to_string());
+    let max_len = constraints.max_len.map(|l| l.to_string());
+
+    if let Some(condition) = between(&length, min_len.as_deref(), max_len.as_deref()) {
+        conditions.push(condition);
+    }
+
+    if let (Some(pattern), Dialect::Postgres) = (&constraints.pattern, dialect) {
+        if let Ok(regex) = translate(pattern, Engine::Postgres) {
+            conditions.push(format!("{} ~ {}", column, quote_literal(&regex)));
+        }
+    }
+
+    if conditions.is_empty() {
+        None
+    } else {
+        Some(format!("CHECK ({})", conditions.join(" AND ")))
+    }
+}
+
+/// The features of the pattern the dialect lacks, which leave the pattern out of the CHECK clause
+///
+/// Patterns translate to PostgreSQL with their meaning kept, e.g. `.` does not match a line feed,
+/// but Unicode classes like `\p{L}` or `\w` do not. SQLite never checks patterns.
+pub fn unsupported(constraints: &ConstraintSet, dialect: Dialect) -> Vec<RegexFeature> {
+    match (&constraints.pattern, dialect) {
+        (Some(pattern), Dialect::Postgres) => translate(pattern, Engine::Postgres).err().unwrap_or_default(),
+        _ => vec![],
+    }
+}
+
+/// The column definition of the constraints, e.g. `"qty" INTEGER NOT NULL CHECK ("qty" BETWEEN 1 AND 1000)`
+pub fn column_definition(column: &str, constraints: &ConstraintSet, dialect: Dialect) -> String {
+    let mut definition = format!("{} {}", quote_identifier(column), column_type(constraints, dialect));
+
+    if !constraints.optional {
+        definition.push_str(" NOT NULL");
+    }
+
+    if let Some(check) = check_constraint(column, constraints, dialect) {
+        definition.push(' ');
+        definition.push_str(&check);
+    }
+
+    definition
+}
+
+fn between(expr: &str, min: Option<&str>, max: Option<&str>) -> Option<String> {
+    match (min, max) {
+        (Some(min), Some(max)) => Some(format!("{} BETWEEN {} AND {}", expr, min, max)),
+        (Some(min), None) => Some(format!("{} >= {}", expr, min)),
+        (None, Some(max)) => Some(format!("{} <= {}", expr, max)),
+        (None, None) => None,
+    }
+}
+
+fn quote_identifier(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+fn quote_literal(literal: &str) -> String {
+    format!("'{}'", literal.replace('\'', "''"))
+}
+
+#[cfg(test)]
+mod test {
+    use fancy_regex::Regex;
+
+    use crate::constraint::{Bound, ConstraintSet};
+    use crate::regex_compat::RegexFeature;
+    use crate::sql::{check_constraint, column_definition, column_type, unsupported, Dialect};
+    use crate::string_like::Pattern;
+
+    #[test]
+    fn it_defines_an_integer_column() {
+        assert_eq!(
+            column_definition("qty", &ConstraintSet::int(1u16, 1000), Dialect::Postgres),
+            r#""qty" INTEGER NOT NULL CHECK ("qty" BETWEEN 1 AND 1000)"#
+        );
+
+        assert_eq!(
+            column_definition("id", &ConstraintSet::int(1u64, u64::MAX), Dialect::Postgres),
+            r#""id" NUMERIC NOT NULL CHECK ("id" BETWEEN 1 AND 18446744073709551615)"#
+        );
+
+        assert_eq!(
+            column_definition("ratio", &ConstraintSet::float(0.0, 0.5), Dialect::Sqlite),
+            r#""ratio" REAL NOT NULL CHECK ("ratio" BETWEEN 0 AND 0.5)"#
+        );
+    }
+
+    #[test]
+    fn it_defines_a_string_column() {
+        let pattern = Pattern::new(Regex::new(r"^[a-z']+$").unwrap());
+
+        assert_eq!(
+            column_definition("handle", &ConstraintSet::string(63).with_pattern(&pattern), Dialect::Postgres),
+            r#""handle" VARCHAR(63) NOT NULL CHECK (char_length("handle") BETWEEN 1 AND 63 AND "handle" ~ '^[a-z'']+$')"#
+        );
+
+        assert_eq!(
+            column_definition("handle", &ConstraintSet::string(63).with_pattern(&pattern), Dialect::Sqlite),
+            r#""handle" TEXT NOT NULL CHECK (length("handle") BETWEEN 1 AND 63)"#
+        );
+
+        assert_eq!(
+            column_definition("bio", &ConstraintSet::string_option(280), Dialect::Sqlite),
+            r#""bio" TEXT CHECK (length("bio") <= 280)"#
+        );
+    }
+
+    #[test]
+    fn it_omits_an_empty_check() {
+        let pattern = Pattern::new(Regex::new(r".+@.+").unwrap());
+
+        assert_eq!(check_constraint("email", &ConstraintSet::string_like(&pattern), Dialect::Sqlite), None);
+    }
+
+    #[test]
+    fn it_drops_infinite_bounds() {
+        let mut constraints = ConstraintSet::float(0.0, 1.0);
+        constraints.max = Some(Bound::Float(f64::INFINITY));
+
+        assert_eq!(
+            check_constraint("ratio", &constraints, Dialect::Postgres),
+            Some(r#"CHECK ("ratio" >= 0)"#.to_string())
+        );
+    }
+
+    #[test]
+    fn it_falls_back_to_text_without_a_valid_varchar_limit() {
+        assert_eq!(column_type(&ConstraintSet::string(0), Dialect::Postgres), "TEXT");
+        assert_eq!(column_type(&ConstraintSet::string(10_485_761), Dialect::Postgres), "TEXT");
+        assert_eq!(column_type(&ConstraintSet::string(10_485_760), Dialect::Postgres), "VARCHAR(10485760)");
+    }
+
+    #[test]
+    fn it_translates_the_pattern_to_postgres() {
+        let pattern = Pattern::new(Regex::new(r"^(?P<c>[a-z])(?P=c).$").unwrap());
+        let constraints = ConstraintSet::string_like(&pattern);
+
+        assert_eq!(
+            check_constraint("handle", &constraints, Dialect::Postgres),
+            Some(r#"CHECK ("handle" ~ '^([a-z])\1[^\n]$')"#.to_string())
+        );
+        assert_eq!(unsupported(&constraints, Dialect::Postgres), vec![]);
+    }
+
+    #[test]
+    fn it_omits_and_reports_an_untranslatable_pattern() {
+        let pattern = Pattern::new(Regex::new(r"^\p{L}+$").unwrap());
+        let constraints = ConstraintSet::string(63).with_pattern(&pattern);
+
+        assert_eq!(
+            check_constraint("name", &constraints, Dialect::Postgres),
+            Some(r#"CHECK (char_length("name") BETWEEN 1 AND 63)"#.to_string())
+        );
+        assert_eq!(unsupported(&constraints, Dialect::Postgres), vec![RegexFeature::UnicodeClass]);
+        assert_eq!(unsupported(&constraints, Dialect::Sqlite), vec![]);
+    }
+}
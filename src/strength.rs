@@ -0,0 +1,192 @@
+//! Password strength estimation
+
+#![deny(missing_docs)]
+
+use zxcvbn::feedback::Suggestion;
+use zxcvbn::zxcvbn;
+
+use crate::error::ConstrainedTypeErrorKind::WeakPassword;
+use crate::error::{ConstrainedTypeError, ConstrainedTypeResult};
+use crate::password::PasswordPolicy;
+use crate::secret::Secret;
+
+/// The estimated strength of a password
+#[derive(Debug, Clone, PartialEq)]
+pub struct Strength {
+    score: u8,
+    guesses_log10: f64,
+    feedback: Vec<String>,
+}
+
+impl Strength {
+    /// Get the score from 0 (too guessable) to 4 (very unguessable)
+    pub fn score(&self) -> u8 {
+        self.score
+    }
+
+    /// Get the order of magnitude of the estimated number of guesses
+    pub fn guesses_log10(&self) -> f64 {
+        self.guesses_log10
+    }
+
+    /// Get the warning and suggestions on how to choose a stronger password
+    pub fn feedback(&self) -> &[String] {
+        &self.feedback
+    }
+}
+
+/// Estimate the strength of a password offline, using bundled dictionaries and common patterns
+///
+/// User inputs such as the username or email address are treated as a dictionary as well.
+pub fn estimate(val: &str, user_inputs: &[&str]) -> Strength {
+    let entropy = zxcvbn(val, user_inputs);
+
+    let mut feedback = vec![];
+
+    if let Some(f) = entropy.feedback() {
+        feedback.extend(f.warning().map(|w| w.to_string()));
+        feedback.extend(f.suggestions().iter().map(|s| s.to_string()));
+    }
+
+    Strength {
+        score: entropy.score().into(),
+        guesses_log10: entropy.guesses_log10(),
+        feedback,
+    }
+}
+
+/// Check a password to have at least the minimum strength score
+pub fn check_strength(
+    field_name: &str,
+    min_score: u8,
+    user_inputs: &[&str],
+    val: &str,
+) -> ConstrainedTypeResult<()> {
+    let strength = estimate(val, user_inputs);
+
+    if strength.score < min_score {
+        let mut feedback = strength.feedback;
+
+        if feedback.is_empty() {
+            feedback.push(Suggestion::AddAnotherWordOrTwo.to_string());
+        }
+
+        return ConstrainedTypeError::from(WeakPassword {
+            field_name: field_name.to_string(),
+            expected: min_score.to_string(),
+            found: strength.score.to_string(),
+            feedback,
+        }).into();
+    }
+
+    Ok(())
+}
+
+/// A builder function constraining a password to satisfy a policy and a minimum strength score
+///
+/// The disallowed terms of the policy are used as user inputs for the estimation.
+/// The error is marked [`sensitive`](ConstrainedTypeError::sensitive).
+pub fn new_strong_password<T, F>(
+    field_name: &str,
+    ctor: F,
+    policy: &PasswordPolicy,
+    min_score: u8,
+    val: &str,
+) -> ConstrainedTypeResult<T>
+    where
+        F: Fn(Secret<String>) -> T,
+{
+    let user_inputs: Vec<&str> = policy.disallowed.iter().map(|t| t.as_str()).collect();
+
+    let mut errors = vec![];
+    errors.extend(policy.check(field_name, val).err());
+    errors.extend(check_strength(field_name, min_score, &user_inputs, val).err());
+
+    if !errors.is_empty() {
+        return ConstrainedTypeError::multiple(errors).sensitive().into();
+    }
+
+    Ok(ctor(Secret::new(val.to_string())))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::ConstrainedTypeErrorKind::{InvalidCharClass, WeakPassword};
+    use crate::strength::estimate;
+
+    mod password {
+        use crate::error::ConstrainedTypeResult;
+        use crate::password::{CharClass, PasswordPolicy};
+        use crate::secret::Secret;
+        use crate::strength::new_strong_password;
+
+        pub(crate) const MIN_SCORE: u8 = 3;
+
+        #[derive(Debug)]
+        pub struct Password(Secret<String>);
+
+        impl Password {
+            pub(crate) fn new(secret: Secret<String>) -> Password {
+                Self(secret)
+            }
+
+            pub fn value(&mut self) -> Option<String> {
+                self.0.consume()
+            }
+        }
+
+        pub fn new(field_name: &str, raw: &str, username: &str) -> ConstrainedTypeResult<Password> {
+            let policy = PasswordPolicy {
+                required: vec![CharClass::Digit],
+                disallowed: vec![username.to_string()],
+                ..PasswordPolicy::default()
+            };
+
+            new_strong_password(field_name, Password::new, &policy, MIN_SCORE, raw)
+        }
+    }
+
+    #[test]
+    fn it_estimates_common_passwords_as_weak() {
+        assert!(estimate("Password1!", &[]).score() < 2);
+        assert!(estimate("correct horse battery staple", &[]).score() > 2);
+    }
+
+    #[test]
+    fn it_errors_on_weak_password_with_feedback() {
+        let err = password::new("password", "Password1!", "john").unwrap_err();
+
+        match err.kind() {
+            WeakPassword { field_name, expected, feedback, .. } => {
+                assert_eq!(field_name, "password");
+                assert_eq!(expected, &password::MIN_SCORE.to_string());
+                assert!(!feedback.is_empty());
+            }
+            kind => panic!("unexpected error kind {:?}", kind),
+        }
+    }
+
+    #[test]
+    fn it_reports_policy_and_strength_errors_together() {
+        let err = password::new("password", "johnsmith", "johnsmith").unwrap_err();
+        let errors = err.errors();
+
+        assert_eq!(errors.len(), 3);
+        assert!(matches!(errors[0].kind(), InvalidCharClass { .. }));
+        assert!(matches!(errors[2].kind(), WeakPassword { .. }));
+    }
+
+    #[test]
+    fn it_marks_errors_as_sensitive() {
+        assert!(password::new("password", "Password1!", "john").unwrap_err().is_sensitive());
+        assert!(password::new("password", "johnsmith", "johnsmith").unwrap_err().is_sensitive());
+    }
+
+    #[test]
+    fn it_can_construct_a_strong_password() {
+        assert_eq!(
+            password::new("password", "7 correct horses stapled", "john").unwrap().value(),
+            Some("7 correct horses stapled".to_string())
+        );
+    }
+}
@@ -2,8 +2,12 @@
 
 #![deny(missing_docs)]
 
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::coerce::Adjustment::Truncated;
+use crate::coerce::Coerced;
 use crate::error::ConstrainedTypeErrorKind::{InvalidMaxLen, InvalidOption};
-use crate::error::{ConstrainedTypeError, ConstrainedTypeResult};
+use crate::error::{ConstrainedTypeError, ConstrainedTypeResult, Span};
 
 /// A builder function constraining a String to be not empty and neither exceeding a character limit
 pub fn new_string<'val, T, F>(
@@ -26,20 +30,79 @@ pub fn new_string<'val, T, F>(
             field_name: field_name.to_string(),
             expected: max_len.to_string(),
             found: val.chars().count().to_string(),
-        }).into();
+        }).with_span(Span::from_char(val, max_len)).into();
     }
 
     Ok(ctor(val))
 }
 
+/// A builder function constraining a String to be not empty and truncating it to a character limit
+///
+/// The String is cut off on a grapheme boundary, so the result may be shorter than the limit
+/// rather than splitting a user-perceived character. It still errors if not even the first
+/// grapheme fits.
+pub fn coerce_string<'val, T, F>(
+    field_name: &str,
+    ctor: F,
+    max_len: usize,
+    val: &'val str,
+) -> ConstrainedTypeResult<Coerced<T>>
+    where
+        F: Fn(&'val str) -> T,
+{
+    if val.is_empty() {
+        return ConstrainedTypeError::from(InvalidOption {
+            field_name: field_name.to_string(),
+        }).into();
+    }
+
+    let len = val.chars().count();
+
+    if len <= max_len {
+        return Ok(Coerced::new(ctor(val), None));
+    }
+
+    let mut end = 0;
+    let mut chars = 0;
+
+    for (idx, grapheme) in val.grapheme_indices(true) {
+        chars += grapheme.chars().count();
+
+        if chars > max_len {
+            break;
+        }
+
+        end = idx + grapheme.len();
+    }
+
+    if end == 0 {
+        let first = val.graphemes(true).next().map_or(0, |g| g.len());
+
+        return ConstrainedTypeError::from(InvalidMaxLen {
+            field_name: field_name.to_string(),
+            expected: max_len.to_string(),
+            found: len.to_string(),
+        }).with_span(Span::new(val, 0, first)).into();
+    }
+
+    Ok(Coerced::new(ctor(&val[..end]), Some(Truncated {
+        field_name: field_name.to_string(),
+        expected: max_len.to_string(),
+        found: len.to_string(),
+    })))
+}
+
 #[cfg(test)]
+#[allow(clippy::redundant_closure)]
 mod test {
-    use crate::error::ConstrainedTypeError;
+    use crate::coerce::Adjustment::Truncated;
+    use crate::error::{ConstrainedTypeError, Span};
     use crate::error::ConstrainedTypeErrorKind::{InvalidMaxLen, InvalidOption};
 
     mod string_5 {
+        use crate::coerce::Coerced;
         use crate::error::ConstrainedTypeResult;
-        use crate::string::new_string;
+        use crate::string::{coerce_string, new_string};
 
         #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
         pub struct String5(String);
@@ -59,6 +122,10 @@ mod test {
         pub fn new(field_name: &str, str: &str) -> ConstrainedTypeResult<String5> {
             new_string(field_name, |v| String5::new(v), String5::MAX_LEN, str)
         }
+
+        pub fn coerce(field_name: &str, str: &str) -> ConstrainedTypeResult<Coerced<String5>> {
+            coerce_string(field_name, |v| String5::new(v), String5::MAX_LEN, str)
+        }
     }
 
     #[test]
@@ -83,8 +150,52 @@ mod test {
         );
     }
 
+    #[test]
+    fn it_spans_the_characters_exceeding_the_string5_max_len() {
+        let err = string_5::new("name", "🐺🐺🐺🐺🐺🐺").unwrap_err();
+
+        assert_eq!(err.span(), Some(Span { start: 20, end: 24, char_start: 5 }));
+    }
+
     #[test]
     fn it_can_construct_a_string5() {
         assert_eq!(string_5::new("name", "🐺").unwrap().value(), "🐺");
     }
+
+    #[test]
+    fn it_truncates_a_string5_on_a_grapheme_boundary() {
+        let name = string_5::coerce("name", "🐺🐺🐺🐺🐺🐺").unwrap();
+
+        assert_eq!(name.value().value(), "🐺🐺🐺🐺🐺");
+        assert_eq!(
+            name.adjustment(),
+            Some(&Truncated {
+                field_name: "name".to_string(),
+                expected: (5).to_string(),
+                found: (6).to_string(),
+            })
+        );
+
+        // "e" followed by a combining acute accent is a single grapheme of two characters
+        let name = string_5::coerce("name", "abcde\u{301}").unwrap();
+
+        assert_eq!(name.value().value(), "abcd");
+    }
+
+    #[test]
+    fn it_spans_a_first_grapheme_exceeding_the_string5_max_len() {
+        let err = string_5::coerce("name", "e\u{301}\u{301}\u{301}\u{301}\u{301}!").unwrap_err();
+
+        assert_eq!(err.span(), Some(Span { start: 0, end: 11, char_start: 0 }));
+    }
+
+    #[test]
+    fn it_does_not_coerce_an_empty_string5() {
+        assert_eq!(
+            string_5::coerce("name", ""),
+            ConstrainedTypeError::from(InvalidOption {
+                field_name: "name".to_string(),
+            }).into()
+        );
+    }
 }
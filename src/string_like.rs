@@ -7,21 +7,87 @@ use fancy_regex::Regex;
 use crate::error::ConstrainedTypeErrorKind::InvalidPattern;
 use crate::error::{ConstrainedTypeError, ConstrainedTypeResult};
 
+/// A regex with an optional human description and rules explaining it to end users
+///
+/// A value matches if it matches the regex and every rule. The messages of violated rules,
+/// or else the description, are shown in the error instead of the raw regex.
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    regex: Regex,
+    description: Option<String>,
+    rules: Vec<(Regex, String)>,
+}
+
+impl Pattern {
+    /// Create a pattern from a regex without description
+    pub fn new(regex: Regex) -> Self {
+        Self {
+            regex,
+            description: None,
+            rules: vec![],
+        }
+    }
+
+    /// Describe the pattern, e.g. `a lower-case name`
+    pub fn with_description<S: Into<String>>(mut self, description: S) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Add a rule a value must match, e.g. `must not end with a hyphen`
+    pub fn with_rule<S: Into<String>>(mut self, regex: Regex, message: S) -> Self {
+        self.rules.push((regex, message.into()));
+        self
+    }
+
+    /// Get the regex of the pattern
+    pub fn regex(&self) -> &Regex {
+        &self.regex
+    }
+
+    /// Get the human description of the pattern
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Get the messages of the rules the value violates
+    fn violated_rules(&self, val: &str) -> Vec<String> {
+        self.rules
+            .iter()
+            .filter(|(regex, _)| !regex.is_match(val).unwrap())
+            .map(|(_, message)| message.to_string())
+            .collect()
+    }
+}
+
+impl From<Regex> for Pattern {
+    fn from(regex: Regex) -> Self {
+        Self::new(regex)
+    }
+}
+
 /// A builder function constraining a String to match a given pattern
-pub fn new_string_like<'val, 'err_val, T, F>(
+///
+/// The error carries no span, as the position where a pattern stops matching is not determinable.
+#[allow(clippy::partialeq_to_none)]
+pub fn new_string_like<'val, 'err_val, T, F, P>(
     field_name: &str,
     ctor: F,
-    pattern: Regex,
+    pattern: P,
     val: &'val str,
     err_val: Option<&'err_val str>,
 ) -> ConstrainedTypeResult<T>
     where
         F: Fn(&'val str) -> T,
+        P: Into<Pattern>,
 {
-    if !pattern.is_match(val).unwrap() {
-        return ConstrainedTypeError::from(InvalidPattern {
+    let pattern = pattern.into();
+    let rules = pattern.violated_rules(val);
+
+    if !rules.is_empty() || !pattern.regex.is_match(val).unwrap() {
+        let mut err = ConstrainedTypeError::from(InvalidPattern {
             field_name: field_name.to_string(),
-            expected: pattern.to_string(),
+            expected: pattern.regex.to_string(),
             found: {
                 if err_val != None {
                     err_val.unwrap().to_string()
@@ -29,16 +95,31 @@ pub fn new_string_like<'val, 'err_val, T, F>(
                     val.to_string()
                 }
             },
-        }).into();
+        });
+
+        if let Some(description) = pattern.description {
+            err = err.with_description(description);
+        }
+
+        if !rules.is_empty() {
+            err = err.with_rules(rules);
+        }
+
+        return err.into();
     }
 
     Ok(ctor(val))
 }
 
+/// Tests of [`new_string_like`]
 #[cfg(test)]
+#[allow(clippy::redundant_closure)]
 pub mod test {
+    use fancy_regex::Regex;
+
     use crate::error::ConstrainedTypeError;
     use crate::error::ConstrainedTypeErrorKind::InvalidPattern;
+    use crate::string_like::{new_string_like, Pattern};
 
     mod constrained_string_like {
         use crate::error::ConstrainedTypeResult;
@@ -92,6 +173,62 @@ pub mod test {
         );
     }
 
+    #[test]
+    fn it_describes_an_invalid_pattern() {
+        let pattern = Pattern::new(Regex::new(r"^[a-z]+[0-9]*$").unwrap())
+            .with_description("a lower-case name optionally followed by a number")
+            .with_rule(Regex::new(r"^[a-z]").unwrap(), "must start with a lower-case letter")
+            .with_rule(Regex::new(r"^.{0,8}$").unwrap(), "must not be longer than 8 characters");
+
+        let err = new_string_like("some_field", |v| v, pattern.clone(), "1abcdefgh", None).unwrap_err();
+
+        assert_eq!(
+            err.rules(),
+            &["must start with a lower-case letter", "must not be longer than 8 characters"]
+        );
+        assert_eq!(
+            err.to_string(),
+            "\"some_field\" must start with a lower-case letter and must not be longer than 8 characters \
+             for value \"1abcdefgh\""
+        );
+
+        let err = new_string_like("some_field", |v| v, pattern, "ab1c", None).unwrap_err();
+
+        assert_eq!(
+            err,
+            ConstrainedTypeError::from(InvalidPattern {
+                field_name: "some_field".to_string(),
+                expected: r"^[a-z]+[0-9]*$".to_string(),
+                found: "ab1c".to_string(),
+            })
+        );
+        assert_eq!(err.description(), Some("a lower-case name optionally followed by a number"));
+        assert_eq!(err.rules(), &[] as &[String]);
+        assert_eq!(
+            err.to_string(),
+            "\"some_field\" must be a lower-case name optionally followed by a number for value \"ab1c\""
+        );
+    }
+
+    #[test]
+    fn it_localizes_an_invalid_pattern() {
+        let pattern = Pattern::new(Regex::new(r"^[a-z]+$").unwrap()).with_description("a lower-case name");
+        let err = new_string_like("name", |v| v, pattern, "Ab", None).unwrap_err();
+
+        let german = |e: &ConstrainedTypeError| match e.kind() {
+            InvalidPattern { field_name, .. } if e.description() == Some("a lower-case name") => {
+                Some(format!("{} muss ein kleingeschriebener Name sein", field_name))
+            }
+            _ => None,
+        };
+
+        assert_eq!(err.localize(&german), "name muss ein kleingeschriebener Name sein");
+
+        let err = new_string_like("name", |v| v, Regex::new(r"^[a-z]+$").unwrap(), "Ab", None).unwrap_err();
+
+        assert_eq!(err.localize(&german), err.to_string());
+    }
+
     #[test]
     fn it_redacts_value_with_error_value() {
         assert_eq!(
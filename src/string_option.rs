@@ -3,9 +3,10 @@
 #![deny(missing_docs)]
 
 use crate::error::ConstrainedTypeErrorKind::InvalidMaxLen;
-use crate::error::{ConstrainedTypeError, ConstrainedTypeResult};
+use crate::error::{ConstrainedTypeError, ConstrainedTypeResult, Span};
 
 /// A builder function constraining an optional String to not exceed a character limit
+#[allow(clippy::partialeq_to_none)]
 pub fn new_string_option<'val, T, F>(
     field_name: &str,
     ctor: F,
@@ -20,15 +21,16 @@ pub fn new_string_option<'val, T, F>(
             field_name: field_name.to_string(),
             expected: max_len.to_string(),
             found: val.unwrap().chars().count().to_string(),
-        }).into();
+        }).with_span(Span::from_char(val.unwrap(), max_len)).into();
     }
 
     Ok(ctor(val))
 }
 
 #[cfg(test)]
+#[allow(clippy::needless_return, clippy::redundant_closure)]
 mod test {
-    use crate::error::ConstrainedTypeError;
+    use crate::error::{ConstrainedTypeError, Span};
     use crate::error::ConstrainedTypeErrorKind::InvalidMaxLen;
 
     mod string_5_option {
@@ -78,6 +80,13 @@ mod test {
         );
     }
 
+    #[test]
+    fn it_spans_the_characters_exceeding_the_string5_option_max_len() {
+        let err = string_5_option::new("name", Some("🐺🐺🐺🐺🐺🐺")).unwrap_err();
+
+        assert_eq!(err.span(), Some(Span { start: 20, end: 24, char_start: 5 }));
+    }
+
     #[test]
     fn it_can_construct_a_string5option_with_some() {
         assert_eq!(
@@ -0,0 +1,267 @@
+//! TypeScript and Zod export
+
+#![deny(missing_docs)]
+
+use crate::constraint::{BaseType, Bound, ConstraintSet, Constraints};
+use crate::regex_compat::{translate, Engine, RegexFeature};
+
+/// The largest integer a JavaScript number holds exactly, `Number.MAX_SAFE_INTEGER`
+const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
+/// Constraints of a registered type which could not be exported
+#[derive(Debug, Clone, PartialEq)]
+pub struct Unsupported {
+    /// Name of the type
+    pub type_name: String,
+    /// Features of the pattern JavaScript lacks
+    pub features: Vec<RegexFeature>,
+    /// Bounds a JavaScript number cannot hold exactly, like integers beyond 2^53 - 1 or infinity
+    pub bounds: Vec<Bound>,
+}
+
+/// Translate a fancy-regex pattern into a JavaScript regex literal, e.g. `/^[a-z]+$/u`
+///
+/// The `u` flag is always set and classes keep their Unicode meaning, e.g. `\d` becomes `\p{Nd}`
+/// and `\w` a class of the same Unicode properties. Named groups `(?P<name>...)` and
+/// backreferences `(?P=name)` are rewritten. Errors with every feature JavaScript lacks.
+pub fn js_regex(pattern: &str) -> Result<String, Vec<RegexFeature>> {
+    translate(pattern, Engine::JavaScript).map(|regex| format!("/{}/u", regex))
+}
+
+/// The bound as a JavaScript number literal, `None` if a number cannot hold it exactly
+fn js_number(bound: Bound) -> Option<String> {
+    let safe = match bound {
+        Bound::Int(val) => val.unsigned_abs() <= MAX_SAFE_INTEGER,
+        Bound::UInt(val) => val <= MAX_SAFE_INTEGER,
+        Bound::Float(val) => val.is_finite(),
+    };
+
+    if safe {
+        Some(bound.to_string())
+    } else {
+        None
+    }
+}
+
+/// The Zod schema of the constraints, e.g. `z.number().int().min(1).max(1000)`
+///
+/// String lengths are refined on code points, like `str::chars`, as Zod's own `.min()` and
+/// `.max()` count UTF-16 code units. Optional values are `.nullish()`, as serde reads a missing
+/// field as `None`. A pattern [`js_regex`] cannot translate and bounds a JavaScript number cannot
+/// hold exactly are left out, see [`Registry::unsupported`].
+pub fn zod_schema(constraints: &ConstraintSet) -> String {
+    let mut schema = match constraints.base_type {
+        BaseType::Integer => "z.number().int()".to_string(),
+        BaseType::Float => "z.number()".to_string(),
+        BaseType::String => "z.string()".to_string(),
+    };
+
+    if let Some(min) = constraints.min.and_then(js_number) {
+        schema.push_str(&format!(".min({})", min));
+    }
+
+    if let Some(max) = constraints.max.and_then(js_number) {
+        schema.push_str(&format!(".max({})", max));
+    }
+
+    if let Some(Ok(regex)) = constraints.pattern.as_deref().map(js_regex) {
+        schema.push_str(&format!(".regex({})", regex));
+    }
+
+    if let Some(min_len) = constraints.min_len {
+        schema.push_str(&format!(".refine((s) => [...s].length >= {})", min_len));
+    }
+
+    if let Some(max_len) = constraints.max_len {
+        schema.push_str(&format!(".refine((s) => [...s].length <= {})", max_len));
+    }
+
+    if let Some(description) = &constraints.description {
+        schema.push_str(&format!(".describe({})", js_string(description)));
+    }
+
+    if constraints.optional {
+        schema.push_str(".nullish()");
+    }
+
+    schema
+}
+
+/// Constrained types to export as TypeScript types with Zod schemas
+#[derive(Debug, Clone, Default)]
+pub struct Registry {
+    types: Vec<(String, ConstraintSet)>,
+}
+
+impl Registry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a constrained type under a name, which must be a valid TypeScript identifier
+    pub fn register<T: Constraints>(self, type_name: &str) -> Self {
+        self.register_constraints(type_name, T::constraints())
+    }
+
+    /// Register constraints under a type name, which must be a valid TypeScript identifier
+    pub fn register_constraints<S: Into<String>>(mut self, type_name: S, constraints: ConstraintSet) -> Self {
+        self.types.push((type_name.into(), constraints));
+        self
+    }
+
+    /// The patterns and bounds of registered types which are left out of the export
+    pub fn unsupported(&self) -> Vec<Unsupported> {
+        self.types
+            .iter()
+            .filter_map(|(type_name, constraints)| {
+                let features = constraints
+                    .pattern
+                    .as_deref()
+                    .and_then(|p| js_regex(p).err())
+                    .unwrap_or_default();
+                let bounds: Vec<Bound> = constraints
+                    .min
+                    .iter()
+                    .chain(constraints.max.iter())
+                    .copied()
+                    .filter(|b| js_number(*b).is_none())
+                    .collect();
+
+                if features.is_empty() && bounds.is_empty() {
+                    return None;
+                }
+
+                Some(Unsupported { type_name: type_name.clone(), features, bounds })
+            })
+            .collect()
+    }
+
+    /// A TypeScript module exporting a Zod schema and an inferred type for each registered type
+    pub fn to_typescript(&self) -> String {
+        let mut module = "import { z } from \"zod\";\n".to_string();
+
+        for (type_name, constraints) in &self.types {
+            module.push_str(&format!(
+                "\nexport const {name} = {schema};\nexport type {name} = z.infer<typeof {name}>;\n",
+                name = type_name,
+                schema = zod_schema(constraints),
+            ));
+        }
+
+        module
+    }
+}
+
+fn js_string(val: &str) -> String {
+    let mut string = "\"".to_string();
+
+    for c in val.chars() {
+        match c {
+            '"' => string.push_str("\\\""),
+            '\\' => string.push_str("\\\\"),
+            '\n' => string.push_str("\\n"),
+            '\r' => string.push_str("\\r"),
+            '\t' => string.push_str("\\t"),
+            c if c.is_control() || c == '\u{2028}' || c == '\u{2029}' => {
+                string.push_str(&format!("\\u{:04x}", c as u32))
+            }
+            c => string.push(c),
+        }
+    }
+
+    string.push('"');
+    string
+}
+
+#[cfg(test)]
+mod test {
+    use fancy_regex::Regex;
+
+    use crate::constraint::{Bound, ConstraintSet};
+    use crate::regex_compat::RegexFeature::{AtomicGroup, Lookbehind, PossessiveQuantifier, WordBoundary};
+    use crate::string_like::Pattern;
+    use crate::typescript::{js_regex, zod_schema, Registry, Unsupported};
+
+    mod slug {
+        use fancy_regex::Regex;
+
+        use crate::constraint::{ConstraintSet, Constraints};
+        use crate::string_like::Pattern;
+
+        pub struct Slug;
+
+        impl Constraints for Slug {
+            fn constraints() -> ConstraintSet {
+                ConstraintSet::string_like(&Pattern::new(Regex::new(r"^(?<!-)[a-z\-]+$").unwrap()))
+            }
+        }
+    }
+
+    #[test]
+    fn it_translates_a_pattern_to_javascript() {
+        assert_eq!(js_regex(r"^[a-z/]+@(?P<host>.+)$"), Ok(r"/^[a-z\/]+@(?<host>[^\n]+)$/u".to_string()));
+        assert_eq!(js_regex(r"^(?P<c>\p{L})(?P=c)$"), Ok(r"/^(?<c>\p{L})\k<c>$/u".to_string()));
+        assert_eq!(js_regex(r"^[+*]+$"), Ok(r"/^[+*]+$/u".to_string()));
+        assert_eq!(js_regex(r"^\d{3}\s\pL$"), Ok(r"/^\p{Nd}{3}\p{White_Space}\p{L}$/u".to_string()));
+    }
+
+    #[test]
+    fn it_flags_features_javascript_lacks() {
+        assert_eq!(js_regex(r"(?<!_)\w++(?>a|b)"), Err(vec![Lookbehind, PossessiveQuantifier, AtomicGroup]));
+        assert_eq!(js_regex(r"\bcat\b"), Err(vec![WordBoundary]));
+    }
+
+    #[test]
+    fn it_leaves_out_bounds_javascript_numbers_cannot_hold() {
+        let mut ratio = ConstraintSet::float(0.0, 0.5);
+        ratio.min = Some(Bound::Float(f64::NEG_INFINITY));
+
+        assert_eq!(zod_schema(&ratio), "z.number().max(0.5)");
+        assert_eq!(zod_schema(&ConstraintSet::int(1u64, u64::MAX)), "z.number().int().min(1)");
+        assert_eq!(
+            zod_schema(&ConstraintSet::int(-(1i64 << 53) + 1, 1 << 53)),
+            "z.number().int().min(-9007199254740991)"
+        );
+
+        assert_eq!(
+            Registry::new().register_constraints("Id", ConstraintSet::int(1u64, u64::MAX)).unsupported(),
+            vec![Unsupported { type_name: "Id".to_string(), features: vec![], bounds: vec![Bound::UInt(u64::MAX)] }]
+        );
+    }
+
+    #[test]
+    fn it_exports_zod_schemas() {
+        let handle = Pattern::new(Regex::new(r"^[a-z]+$").unwrap()).with_description("a \"handle\"");
+
+        assert_eq!(zod_schema(&ConstraintSet::int(1u16, 1000)), "z.number().int().min(1).max(1000)");
+        assert_eq!(
+            zod_schema(&ConstraintSet::string_option(280)),
+            "z.string().refine((s) => [...s].length <= 280).nullish()"
+        );
+
+        let registry = Registry::new()
+            .register_constraints("Handle", ConstraintSet::string(63).with_pattern(&handle))
+            .register::<slug::Slug>("Slug");
+
+        assert_eq!(
+            registry.to_typescript(),
+            concat!(
+                "import { z } from \"zod\";\n",
+                "\n",
+                "export const Handle = z.string().regex(/^[a-z]+$/u)",
+                ".refine((s) => [...s].length >= 1).refine((s) => [...s].length <= 63)",
+                ".describe(\"a \\\"handle\\\"\");\n",
+                "export type Handle = z.infer<typeof Handle>;\n",
+                "\n",
+                "export const Slug = z.string();\n",
+                "export type Slug = z.infer<typeof Slug>;\n",
+            )
+        );
+
+        assert_eq!(
+            registry.unsupported(),
+            vec![Unsupported { type_name: "Slug".to_string(), features: vec![Lookbehind], bounds: vec![] }]
+        );
+    }
+}
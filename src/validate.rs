@@ -0,0 +1,14 @@
+//! Validation of unvalidated representations
+
+#![deny(missing_docs)]
+
+use crate::error::ConstrainedTypeResult;
+
+/// A value object, or a request made of value objects, built from its unvalidated representation
+pub trait Validate: Sized {
+    /// The unvalidated representation, e.g. deserialized from a request or parsed from an argument
+    type Raw;
+
+    /// Validate the raw representation, e.g. by calling the `new_*` builders of each field
+    fn validate(field_name: &str, raw: Self::Raw) -> ConstrainedTypeResult<Self>;
+}
@@ -0,0 +1,256 @@
+//! Axum extractors
+
+#![deny(missing_docs)]
+
+use axum::extract::rejection::{JsonRejection, PathRejection, QueryRejection};
+use axum::extract::{FromRequest, FromRequestParts, Path, Query, Request};
+use axum::http::request::Parts;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::error::ConstrainedTypeError;
+pub use crate::validate::Validate;
+
+/// An error reported against a single field, as listed in the body of a rejection
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldError {
+    /// Field path shown in the error, e.g. `lines[3]`
+    pub field: String,
+    /// Machine-readable code of the error kind, e.g. `invalid_max_len`
+    pub code: String,
+    /// Human-readable message
+    pub message: String,
+}
+
+/// The body of a rejection, listing every error
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorBody {
+    /// Errors of each field
+    pub errors: Vec<FieldError>,
+}
+
+impl From<&ConstrainedTypeError> for ErrorBody {
+    fn from(err: &ConstrainedTypeError) -> Self {
+        let errors = err
+            .errors()
+            .into_iter()
+            .map(|e| FieldError {
+                field: e.kind().field_name().unwrap_or_default().to_string(),
+                code: e.kind().code().to_string(),
+                message: e.to_redacted_string(),
+            })
+            .collect();
+
+        Self { errors }
+    }
+}
+
+/// The rejection of a validating extractor
+#[derive(Debug)]
+pub enum ValidationRejection {
+    /// The JSON body could not be deserialized
+    Json(JsonRejection),
+    /// The query string could not be deserialized
+    Query(QueryRejection),
+    /// The path parameters could not be deserialized
+    Path(PathRejection),
+    /// The deserialized value violates a constraint, responded with 422 Unprocessable Entity
+    Invalid(ConstrainedTypeError),
+}
+
+impl IntoResponse for ValidationRejection {
+    fn into_response(self) -> Response {
+        match self {
+            ValidationRejection::Json(rejection) => rejection.into_response(),
+            ValidationRejection::Query(rejection) => rejection.into_response(),
+            ValidationRejection::Path(rejection) => rejection.into_response(),
+            ValidationRejection::Invalid(err) => {
+                (StatusCode::UNPROCESSABLE_ENTITY, Json(ErrorBody::from(&err))).into_response()
+            }
+        }
+    }
+}
+
+/// Deserializes and validates the JSON body, passing `body` as field name
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValidJson<T>(pub T);
+
+impl<T, S> FromRequest<S> for ValidJson<T>
+    where
+        T: Validate,
+        T::Raw: DeserializeOwned,
+        S: Send + Sync,
+{
+    type Rejection = ValidationRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(raw) = Json::<T::Raw>::from_request(req, state).await.map_err(ValidationRejection::Json)?;
+
+        T::validate("body", raw).map(ValidJson).map_err(ValidationRejection::Invalid)
+    }
+}
+
+/// Deserializes and validates the query string, passing `query` as field name
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValidQuery<T>(pub T);
+
+impl<T, S> FromRequestParts<S> for ValidQuery<T>
+    where
+        T: Validate,
+        T::Raw: DeserializeOwned,
+        S: Send + Sync,
+{
+    type Rejection = ValidationRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Query(raw) = Query::<T::Raw>::from_request_parts(parts, state).await.map_err(ValidationRejection::Query)?;
+
+        T::validate("query", raw).map(ValidQuery).map_err(ValidationRejection::Invalid)
+    }
+}
+
+/// Deserializes and validates the path parameters, passing `path` as field name
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValidPath<T>(pub T);
+
+impl<T, S> FromRequestParts<S> for ValidPath<T>
+    where
+        T: Validate,
+        T::Raw: DeserializeOwned + Send,
+        S: Send + Sync,
+{
+    type Rejection = ValidationRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Path(raw) = Path::<T::Raw>::from_request_parts(parts, state).await.map_err(ValidationRejection::Path)?;
+
+        T::validate("path", raw).map(ValidPath).map_err(ValidationRejection::Invalid)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use axum::body::{to_bytes, Body};
+    use axum::extract::{FromRequest, FromRequestParts, Request};
+    use axum::http::StatusCode;
+    use axum::response::IntoResponse;
+
+    use crate::error::ConstrainedTypeError;
+    use crate::error::ConstrainedTypeErrorKind::InvalidPattern;
+    use crate::web::{ErrorBody, FieldError, ValidJson, ValidQuery};
+
+    mod signup {
+        use fancy_regex::Regex;
+        use serde::Deserialize;
+
+        use crate::error::{ConstrainedTypeError, ConstrainedTypeResult};
+        use crate::int::new_int;
+        use crate::string_like::new_string_like;
+        use crate::validate::Validate;
+
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct Signup {
+            pub handle: String,
+            pub age: u8,
+        }
+
+        #[derive(Deserialize)]
+        pub struct RawSignup {
+            handle: String,
+            age: u8,
+        }
+
+        impl Validate for Signup {
+            type Raw = RawSignup;
+
+            fn validate(_: &str, raw: RawSignup) -> ConstrainedTypeResult<Signup> {
+                let handle = new_string_like("handle", str::to_string, Regex::new(r"^[a-z]+$").unwrap(), &raw.handle, None);
+                let age = new_int("age", |v| v, 18, 130, raw.age);
+
+                match (handle, age) {
+                    (Ok(handle), Ok(age)) => Ok(Signup { handle, age }),
+                    (handle, age) => Err(ConstrainedTypeError::multiple(
+                        vec![handle.err(), age.err()].into_iter().flatten().collect()
+                    )),
+                }
+            }
+        }
+    }
+
+    fn json(body: &str) -> Request {
+        Request::builder()
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn it_rejects_every_invalid_field_with_422() {
+        let rejection = ValidJson::<signup::Signup>::from_request(json(r#"{"handle":"Admin","age":12}"#), &())
+            .await
+            .unwrap_err()
+            .into_response();
+
+        assert_eq!(rejection.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let body: ErrorBody = serde_json::from_slice(&to_bytes(rejection.into_body(), usize::MAX).await.unwrap()).unwrap();
+
+        assert_eq!(
+            body.errors,
+            vec![
+                FieldError {
+                    field: "handle".to_string(),
+                    code: "invalid_pattern".to_string(),
+                    message: "\"handle\" does not match pattern \"^[a-z]+$\" for value \"Admin\"".to_string(),
+                },
+                FieldError {
+                    field: "age".to_string(),
+                    code: "invalid_min_val".to_string(),
+                    message: "\"age\" must not be less than \"18\", \"12\"".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn it_redacts_the_message_of_sensitive_errors() {
+        let err = ConstrainedTypeError::from(InvalidPattern {
+            field_name: "api_key".to_string(),
+            expected: "^key_[a-z0-9]+$".to_string(),
+            found: "key_SECRET".to_string(),
+        }).sensitive();
+
+        assert_eq!(
+            ErrorBody::from(&err).errors[0].message,
+            "\"api_key\" does not match pattern \"^key_[a-z0-9]+$\" for value \"<redacted>\""
+        );
+    }
+
+    #[tokio::test]
+    async fn it_keeps_the_deserialization_rejection() {
+        let rejection = ValidJson::<signup::Signup>::from_request(json(r#"{"handle":"jane"}"#), &())
+            .await
+            .unwrap_err()
+            .into_response();
+
+        assert_eq!(rejection.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert!(serde_json::from_slice::<ErrorBody>(&to_bytes(rejection.into_body(), usize::MAX).await.unwrap()).is_err());
+    }
+
+    #[tokio::test]
+    async fn it_extracts_a_valid_signup() {
+        let ValidJson(signup) = ValidJson::<signup::Signup>::from_request(json(r#"{"handle":"jane","age":42}"#), &())
+            .await
+            .unwrap();
+
+        assert_eq!(signup, signup::Signup { handle: "jane".to_string(), age: 42 });
+
+        let (mut parts, _) = Request::builder().uri("/signup?handle=jane&age=42").body(()).unwrap().into_parts();
+        let ValidQuery(signup) = ValidQuery::<signup::Signup>::from_request_parts(&mut parts, &()).await.unwrap();
+
+        assert_eq!(signup.handle, "jane");
+    }
+}